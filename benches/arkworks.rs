@@ -0,0 +1,103 @@
+//! RCB15 against the native arkworks (Jacobian) formulas.
+//!
+//! Every benchmark group holds an `rcb15` and a `native` function on the
+//! same inputs; `cargo run --release --example bench_report` summarises the
+//! ratio between the two.
+
+use ark_ec::short_weierstrass_jacobian::GroupProjective;
+use ark_ec::ProjectiveCurve;
+use ark_ff::PrimeField;
+use ark_std::{test_rng, UniformRand};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use swcurve_rcb15::arkworks::{
+    add, add_mixed, affine_to_homogeneous_form, double, mul, naive_msm, Rcb15Params,
+};
+
+const MSM_LOG_SIZES: [usize; 3] = [2, 4, 6];
+
+/// A homogeneous representation of `p` with a random Z.
+fn homogeneous<P: Rcb15Params>(p: &GroupProjective<P>) -> GroupProjective<P> {
+    let l = P::BaseField::rand(&mut test_rng());
+    let p = affine_to_homogeneous_form(&p.into_affine());
+    GroupProjective::<P>::new(p.x * l, p.y * l, p.z * l)
+}
+
+fn bench_curve<P: Rcb15Params>(c: &mut Criterion, curve: &str) {
+    let mut rng = test_rng();
+    let x = GroupProjective::<P>::rand(&mut rng);
+    let y = GroupProjective::<P>::rand(&mut rng);
+    let y_affine = y.into_affine();
+    let s = P::ScalarField::rand(&mut rng);
+    let (x_rcb, y_rcb) = (homogeneous(&x), homogeneous(&y));
+
+    let mut group = c.benchmark_group(format!("arkworks {curve} add"));
+    group.bench_function("rcb15", |b| {
+        b.iter(|| add(black_box(&x_rcb), black_box(&y_rcb)))
+    });
+    group.bench_function("native", |b| b.iter(|| black_box(x) + black_box(y)));
+    group.finish();
+
+    let mut group = c.benchmark_group(format!("arkworks {curve} mixed add"));
+    group.bench_function("rcb15", |b| {
+        b.iter(|| add_mixed(black_box(&x_rcb), black_box(&y_affine)))
+    });
+    group.bench_function("native", |b| {
+        b.iter(|| {
+            let mut res = black_box(x);
+            res.add_assign_mixed(black_box(&y_affine));
+            res
+        })
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group(format!("arkworks {curve} double"));
+    group.bench_function("rcb15", |b| b.iter(|| double(black_box(&x_rcb))));
+    group.bench_function("native", |b| b.iter(|| black_box(x).double()));
+    group.finish();
+
+    let mut group = c.benchmark_group(format!("arkworks {curve} mul"));
+    group.bench_function("rcb15", |b| {
+        b.iter(|| mul(black_box(&x_rcb), black_box(&s)))
+    });
+    group.bench_function("native", |b| {
+        b.iter(|| black_box(x).mul(black_box(s).into_repr()))
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group(format!("arkworks {curve} msm"));
+    for log_size in MSM_LOG_SIZES {
+        let size = 1 << log_size;
+        let bases: Vec<_> = (0..size)
+            .map(|_| GroupProjective::<P>::rand(&mut rng))
+            .collect();
+        let bases_rcb: Vec<_> = bases.iter().map(homogeneous).collect();
+        let scalars: Vec<_> = (0..size).map(|_| P::ScalarField::rand(&mut rng)).collect();
+
+        group.bench_with_input(BenchmarkId::new("rcb15", size), &size, |b, _| {
+            b.iter(|| naive_msm(black_box(&bases_rcb), black_box(&scalars)))
+        });
+        // the same naive sum of products, with native arithmetic
+        group.bench_with_input(BenchmarkId::new("native", size), &size, |b, _| {
+            b.iter(|| {
+                bases
+                    .iter()
+                    .zip(scalars.iter())
+                    .map(|(p, s)| p.mul(s.into_repr()))
+                    .sum::<GroupProjective<P>>()
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_arkworks(c: &mut Criterion) {
+    bench_curve::<ark_bn254::g1::Parameters>(c, "bn254");
+    bench_curve::<ark_bls12_381::g1::Parameters>(c, "bls12-381");
+    bench_curve::<ark_bls12_381::g2::Parameters>(c, "bls12-381 g2");
+    bench_curve::<ark_bls12_377::g1::Parameters>(c, "bls12-377");
+    bench_curve::<ark_bls12_377::g2::Parameters>(c, "bls12-377 g2");
+}
+
+criterion_group!(benches, bench_arkworks);
+criterion_main!(benches);
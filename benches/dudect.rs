@@ -0,0 +1,191 @@
+//! A dudect-style timing leakage test of the scalar multiplications of both
+//! backends.
+//!
+//! Every measurement times one multiplication by a scalar of one of two
+//! classes, a fixed scalar of low Hamming weight or a uniform one, in random
+//! order. Welch's t-test then compares the two timing distributions, after
+//! cropping the slow tail at a few percentiles as dudect does. A |t| above
+//! 4.5 is evidence of leakage: `mul` skips the additions of zero bits and is
+//! expected to leak, `halo2::mul_homogeneous` and the `mul_blinded` of both
+//! backends are not.
+//!
+//! ```text
+//! cargo bench --bench dudect
+//! DUDECT_SAMPLES=100000 cargo bench --bench dudect
+//! ```
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use ark_ff::One;
+use ark_std::rand::{Rng, RngCore};
+use ark_std::{test_rng, UniformRand};
+use halo2curves::bn256;
+use halo2curves::group::ff::Field;
+use halo2curves::pasta::pallas;
+
+use swcurve_rcb15::{arkworks, halo2};
+
+const DEFAULT_SAMPLES: usize = 10_000;
+const THRESHOLD: f64 = 4.5;
+/// The tail is cropped above these percentiles, plus no cropping at all.
+const PERCENTILES: [f64; 4] = [0.5, 0.75, 0.9, 1.0];
+
+/// Welch's t-test with running means and variances (Welford).
+#[derive(Default)]
+struct TTest {
+    n: [f64; 2],
+    mean: [f64; 2],
+    m2: [f64; 2],
+}
+
+impl TTest {
+    fn push(&mut self, class: usize, x: f64) {
+        self.n[class] += 1.0;
+        let delta = x - self.mean[class];
+        self.mean[class] += delta / self.n[class];
+        self.m2[class] += delta * (x - self.mean[class]);
+    }
+
+    fn t(&self) -> f64 {
+        let var = |i: usize| self.m2[i] / (self.n[i] - 1.0);
+        (self.mean[0] - self.mean[1]) / (var(0) / self.n[0] + var(1) / self.n[1]).sqrt()
+    }
+}
+
+/// The largest |t| over the cropped sets of `(class, nanoseconds)`.
+fn max_t(measurements: &[(usize, u128)]) -> f64 {
+    let mut sorted: Vec<u128> = measurements.iter().map(|(_, t)| *t).collect();
+    sorted.sort_unstable();
+    PERCENTILES
+        .iter()
+        .map(|p| {
+            let cutoff = sorted[((sorted.len() - 1) as f64 * p) as usize];
+            let mut test = TTest::default();
+            for (class, t) in measurements.iter().filter(|(_, t)| *t <= cutoff) {
+                test.push(*class, *t as f64);
+            }
+            test.t().abs()
+        })
+        .fold(0.0, f64::max)
+}
+
+/// Times `f` on `samples` inputs of random classes: class 0 is `fixed`,
+/// class 1 a uniform scalar drawn with `random`.
+fn measure<S: Copy>(
+    samples: usize,
+    fixed: S,
+    mut random: impl FnMut(&mut dyn RngCore) -> S,
+    mut f: impl FnMut(&S) -> u128,
+) -> Vec<(usize, u128)> {
+    let mut rng = test_rng();
+    // draw the inputs first, so that sampling is not timed
+    let inputs: Vec<(usize, S)> = (0..samples)
+        .map(|_| {
+            if rng.gen::<bool>() {
+                (0, fixed)
+            } else {
+                (1, random(&mut rng))
+            }
+        })
+        .collect();
+    inputs.iter().map(|(class, s)| (*class, f(s))).collect()
+}
+
+fn time(f: impl FnOnce()) -> u128 {
+    let start = Instant::now();
+    f();
+    start.elapsed().as_nanos()
+}
+
+fn print_verdicts(curve: &str, samples: usize, variants: &[(&str, Vec<(usize, u128)>)]) {
+    for (name, measurements) in variants {
+        let t = max_t(measurements);
+        let verdict = if t > THRESHOLD {
+            "leakage"
+        } else {
+            "no evidence of leakage"
+        };
+        println!("{curve} {name}: max |t| = {t:.2} over {samples} samples, {verdict}");
+    }
+}
+
+fn report_halo2<C: halo2::Rcb15Params>(curve: &str, samples: usize) {
+    let mut rng = test_rng();
+    let base = C::random(&mut rng);
+    let base_homogeneous = halo2::HomogeneousPoint::from(base);
+    let fixed = C::ScalarExt::ONE;
+    let random = |rng: &mut dyn RngCore| C::ScalarExt::random(rng);
+
+    let variants = [
+        (
+            "halo2::mul",
+            measure(samples, fixed, random, |s| {
+                time(|| {
+                    black_box(halo2::mul(black_box(&base), black_box(s)));
+                })
+            }),
+        ),
+        (
+            "halo2::mul_homogeneous",
+            measure(samples, fixed, random, |s| {
+                time(|| {
+                    black_box(halo2::mul_homogeneous(
+                        black_box(&base_homogeneous),
+                        black_box(s),
+                    ));
+                })
+            }),
+        ),
+        (
+            "halo2::mul_blinded",
+            measure(samples, fixed, random, |s| {
+                time(|| {
+                    black_box(halo2::mul_blinded(black_box(&base), black_box(s), &mut rng));
+                })
+            }),
+        ),
+    ];
+    print_verdicts(curve, samples, &variants);
+}
+
+fn report_arkworks<P: arkworks::Rcb15Params>(curve: &str, samples: usize) {
+    let mut rng = test_rng();
+    let base = arkworks::HomogeneousPoint::<P>::rand(&mut rng).0;
+    let fixed = P::ScalarField::one();
+    let random = |rng: &mut dyn RngCore| P::ScalarField::rand(rng);
+
+    let variants = [
+        (
+            "arkworks::mul",
+            measure(samples, fixed, random, |s| {
+                time(|| {
+                    let _ = black_box(arkworks::mul(black_box(&base), black_box(s)));
+                })
+            }),
+        ),
+        (
+            "arkworks::mul_blinded",
+            measure(samples, fixed, random, |s| {
+                time(|| {
+                    let _ = black_box(arkworks::mul_blinded(
+                        black_box(&base),
+                        black_box(s),
+                        &mut rng,
+                    ));
+                })
+            }),
+        ),
+    ];
+    print_verdicts(curve, samples, &variants);
+}
+
+fn main() {
+    let samples = std::env::var("DUDECT_SAMPLES")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(DEFAULT_SAMPLES);
+    report_halo2::<bn256::G1>("bn256", samples);
+    report_halo2::<pallas::Point>("pallas", samples);
+    report_arkworks::<ark_bn254::g1::Parameters>("bn254", samples);
+}
@@ -0,0 +1,81 @@
+//! RCB15 against the native halo2curves (Jacobian) formulas.
+//!
+//! Every benchmark group holds an `rcb15` and a `native` function on the
+//! same inputs; `cargo run --release --example bench_report` summarises the
+//! ratio between the two.
+
+use ark_std::test_rng;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use halo2curves::bn256;
+use halo2curves::group::ff::Field;
+use halo2curves::pasta::{pallas, vesta};
+use halo2curves::CurveAffine;
+
+use swcurve_rcb15::halo2::{add, add_mixed, double, mul, naive_msm, Rcb15Params};
+
+const MSM_LOG_SIZES: [usize; 3] = [2, 4, 6];
+
+fn bench_curve<C>(c: &mut Criterion, curve: &str)
+where
+    C: Rcb15Params,
+    C::AffineExt: CurveAffine<Base = C::Base>,
+{
+    let mut rng = test_rng();
+    let x = C::random(&mut rng);
+    let y = C::random(&mut rng);
+    let y_affine = y.to_affine();
+    let s = C::ScalarExt::random(&mut rng);
+
+    let mut group = c.benchmark_group(format!("halo2 {curve} add"));
+    group.bench_function("rcb15", |b| b.iter(|| add(black_box(&x), black_box(&y))));
+    group.bench_function("native", |b| b.iter(|| black_box(x) + black_box(y)));
+    group.finish();
+
+    let mut group = c.benchmark_group(format!("halo2 {curve} mixed add"));
+    group.bench_function("rcb15", |b| {
+        b.iter(|| add_mixed(black_box(&x), black_box(&y_affine)))
+    });
+    group.bench_function("native", |b| b.iter(|| black_box(x) + black_box(y_affine)));
+    group.finish();
+
+    let mut group = c.benchmark_group(format!("halo2 {curve} double"));
+    group.bench_function("rcb15", |b| b.iter(|| double(black_box(&x))));
+    group.bench_function("native", |b| b.iter(|| black_box(x).double()));
+    group.finish();
+
+    let mut group = c.benchmark_group(format!("halo2 {curve} mul"));
+    group.bench_function("rcb15", |b| b.iter(|| mul(black_box(&x), black_box(&s))));
+    group.bench_function("native", |b| b.iter(|| black_box(x) * black_box(s)));
+    group.finish();
+
+    let mut group = c.benchmark_group(format!("halo2 {curve} msm"));
+    for log_size in MSM_LOG_SIZES {
+        let size = 1 << log_size;
+        let bases: Vec<_> = (0..size).map(|_| C::random(&mut rng)).collect();
+        let scalars: Vec<_> = (0..size).map(|_| C::ScalarExt::random(&mut rng)).collect();
+
+        group.bench_with_input(BenchmarkId::new("rcb15", size), &size, |b, _| {
+            b.iter(|| naive_msm(black_box(&bases), black_box(&scalars)))
+        });
+        // the same naive sum of products, with native arithmetic
+        group.bench_with_input(BenchmarkId::new("native", size), &size, |b, _| {
+            b.iter(|| {
+                bases
+                    .iter()
+                    .zip(scalars.iter())
+                    .map(|(p, s)| *p * s)
+                    .fold(C::identity(), |acc, p| acc + p)
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_halo2(c: &mut Criterion) {
+    bench_curve::<bn256::G1>(c, "bn256");
+    bench_curve::<pallas::Point>(c, "pallas");
+    bench_curve::<vesta::Point>(c, "vesta");
+}
+
+criterion_group!(benches, bench_halo2);
+criterion_main!(benches);
@@ -0,0 +1,119 @@
+//! Summarise the criterion results of `cargo bench` as a markdown table of
+//! the relative cost of RCB15 against the native formulas.
+//!
+//! ```text
+//! cargo bench
+//! cargo run --release --example bench_report [target/criterion]
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+/// Every curve that `cargo bench` measures, as named in its benchmark groups.
+const CURVES: [&str; 8] = [
+    "arkworks bn254",
+    "arkworks bls12-381",
+    "arkworks bls12-381 g2",
+    "arkworks bls12-377",
+    "arkworks bls12-377 g2",
+    "halo2 bn256",
+    "halo2 pallas",
+    "halo2 vesta",
+];
+const OPERATIONS: [&str; 5] = ["add", "mixed add", "double", "mul", "msm"];
+
+/// Mean time in nanoseconds, keyed by (group, input size) and then function.
+type Results = BTreeMap<(String, Option<u64>), BTreeMap<String, f64>>;
+
+fn read_json(path: &Path) -> Option<Value> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+/// Criterion writes `new/benchmark.json` and `new/estimates.json` for every
+/// benchmark, nested by group, function and input.
+fn collect(dir: &Path, results: &mut Results) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        if path.file_name().is_some_and(|name| name == "new") {
+            let (benchmark, estimates) = match (
+                read_json(&path.join("benchmark.json")),
+                read_json(&path.join("estimates.json")),
+            ) {
+                (Some(benchmark), Some(estimates)) => (benchmark, estimates),
+                _ => continue,
+            };
+            let field = |name: &str| benchmark[name].as_str().unwrap_or_default().to_string();
+            if let Some(mean) = estimates["mean"]["point_estimate"].as_f64() {
+                results
+                    .entry((field("group_id"), field("value_str").parse().ok()))
+                    .or_default()
+                    .insert(field("function_id"), mean);
+            }
+        } else {
+            collect(&path, results);
+        }
+    }
+}
+
+fn format_time(ns: f64) -> String {
+    if ns >= 1e6 {
+        format!("{:.2} ms", ns / 1e6)
+    } else if ns >= 1e3 {
+        format!("{:.2} us", ns / 1e3)
+    } else {
+        format!("{:.1} ns", ns)
+    }
+}
+
+fn main() {
+    let dir = std::env::args()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("target/criterion"));
+
+    let mut results = Results::new();
+    collect(&dir, &mut results);
+    if results.is_empty() {
+        eprintln!(
+            "no criterion results under {}; run `cargo bench` first",
+            dir.display()
+        );
+        std::process::exit(1);
+    }
+
+    for curve in CURVES {
+        let measured = results.keys().any(|(group, _)| {
+            OPERATIONS
+                .iter()
+                .any(|op| *group == format!("{curve} {op}"))
+        });
+        if !measured {
+            eprintln!("no results for {curve}");
+        }
+    }
+
+    println!("| benchmark | size | rcb15 | native | rcb15 / native |");
+    println!("|---|---:|---:|---:|---:|");
+    for ((group, size), functions) in &results {
+        if let (Some(rcb15), Some(native)) = (functions.get("rcb15"), functions.get("native")) {
+            println!(
+                "| {} | {} | {} | {} | {:.2} |",
+                group,
+                size.map(|size| size.to_string()).unwrap_or_default(),
+                format_time(*rcb15),
+                format_time(*native),
+                rcb15 / native
+            );
+        }
+    }
+}
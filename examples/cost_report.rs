@@ -0,0 +1,31 @@
+//! Print the field-operation counts and circuit costs of every formula as a
+//! markdown table, per algorithm and per coordinate system.
+//!
+//! ```text
+//! cargo run --example cost_report
+//! ```
+
+use swcurve_rcb15::opcount::{circuit_cost, Coordinates, Formula};
+
+fn main() {
+    println!(
+        "| formula | coordinates | nonlinear | linear | constant | R1CS constraints | PLONK gates |"
+    );
+    println!("|---|---|---:|---:|---:|---:|---:|");
+    for formula in Formula::ALL {
+        for coordinates in Coordinates::ALL {
+            let cost = circuit_cost(formula, coordinates);
+            println!(
+                "| Algorithm {} ({}) | {:?} | {} | {} | {} | {} | {} |",
+                formula.algorithm(),
+                formula.program().name,
+                coordinates,
+                cost.nonlinear,
+                cost.linear,
+                cost.constant,
+                cost.r1cs_constraints(),
+                cost.plonk_gates()
+            );
+        }
+    }
+}
@@ -0,0 +1,185 @@
+//! Differential checks of the RCB15 formulas against native curve arithmetic,
+//! driven by the cargo-fuzz targets in `fuzz_targets/`.
+//!
+//! An input is decoded as
+//!
+//! ```text
+//! curve:u8  point(a)  point(b)  scalar(s)  n:u8  n * (point, scalar)
+//! point  = flags:u8 k:[u8; 32] l:[u8; 8]
+//! scalar = [u8; 32]
+//! ```
+//!
+//! A point is `[k]G`, or the identity if bit 0 of `flags` is set, negated if
+//! bit 1 is set. It is handed to RCB15 in a non-normalised representation
+//! scaled by `l`. Missing bytes read as zero, so every input decodes.
+
+use std::ops::{Add, Mul};
+
+use ark_ec::short_weierstrass_jacobian::GroupProjective;
+use ark_ec::ProjectiveCurve;
+use ark_ff::{PrimeField, Zero};
+use halo2curves::bn256;
+use halo2curves::pasta::{pallas, vesta};
+
+use swcurve_rcb15::arkworks::{self, affine_to_homogeneous_form, homogeneous_form_to_affine};
+use swcurve_rcb15::halo2;
+
+/// Upper bound on the number of terms in a decoded MSM.
+const MAX_MSM: u8 = 8;
+
+const FLAG_IDENTITY: u8 = 1;
+const FLAG_NEGATE: u8 = 2;
+
+struct Input<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Input<'a> {
+    fn byte(&mut self) -> u8 {
+        self.bytes::<1>()[0]
+    }
+
+    fn bytes<const N: usize>(&mut self) -> [u8; N] {
+        let mut out = [0u8; N];
+        let n = N.min(self.data.len());
+        out[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        out
+    }
+
+    fn scalar<F>(&mut self) -> F
+    where
+        F: From<u64> + Add<Output = F> + Mul<Output = F> + Copy,
+    {
+        let bytes = self.bytes::<32>();
+        let half = F::from(1u64 << 32);
+        bytes.chunks(8).rev().fold(F::from(0u64), |acc, limb| {
+            acc * half * half + F::from(u64::from_le_bytes(limb.try_into().unwrap()))
+        })
+    }
+
+    /// A non-zero scaling factor for the projective representation.
+    fn scaling<F: From<u64>>(&mut self) -> F {
+        F::from(u64::from_le_bytes(self.bytes::<8>()).max(1))
+    }
+
+    fn msm_len(&mut self) -> usize {
+        (self.byte() % MAX_MSM) as usize + 1
+    }
+}
+
+pub fn check_arkworks(data: &[u8]) {
+    let mut input = Input { data };
+    match input.byte() % 3 {
+        0 => check_arkworks_curve::<ark_bn254::g1::Parameters>(&mut input),
+        1 => check_arkworks_curve::<ark_bls12_381::g1::Parameters>(&mut input),
+        _ => check_arkworks_curve::<ark_bls12_377::g1::Parameters>(&mut input),
+    }
+}
+
+pub fn check_halo2(data: &[u8]) {
+    let mut input = Input { data };
+    match input.byte() % 3 {
+        0 => check_halo2_curve::<bn256::G1>(&mut input),
+        1 => check_halo2_curve::<pallas::Point>(&mut input),
+        _ => check_halo2_curve::<vesta::Point>(&mut input),
+    }
+}
+
+/// Returns the point in arkworks' native (Jacobian) form and in a scaled
+/// homogeneous form for RCB15.
+fn ark_point<P: arkworks::Rcb15Params>(
+    input: &mut Input,
+) -> (GroupProjective<P>, GroupProjective<P>) {
+    let flags = input.byte();
+    let k: P::ScalarField = input.scalar();
+    let l: P::BaseField = input.scaling();
+
+    let mut native = if flags & FLAG_IDENTITY != 0 {
+        GroupProjective::<P>::zero()
+    } else {
+        GroupProjective::<P>::prime_subgroup_generator().mul(k.into_repr())
+    };
+    if flags & FLAG_NEGATE != 0 {
+        native = -native;
+    }
+
+    let p = affine_to_homogeneous_form(&native.into_affine());
+    (native, GroupProjective::<P>::new(p.x * l, p.y * l, p.z * l))
+}
+
+fn check_arkworks_curve<P: arkworks::Rcb15Params>(input: &mut Input) {
+    let (a, a_rcb) = ark_point::<P>(input);
+    let (b, b_rcb) = ark_point::<P>(input);
+    let s: P::ScalarField = input.scalar();
+
+    let res = arkworks::add(&a_rcb, &b_rcb);
+    assert_eq!(homogeneous_form_to_affine(&res), (a + b).into_affine());
+
+    let res = arkworks::double(&a_rcb);
+    assert_eq!(homogeneous_form_to_affine(&res), a.double().into_affine());
+
+    let res = arkworks::mul(&a_rcb, &s);
+    assert_eq!(
+        homogeneous_form_to_affine(&res),
+        a.mul(s.into_repr()).into_affine()
+    );
+
+    let n = input.msm_len();
+    let mut bases = Vec::with_capacity(n);
+    let mut scalars = Vec::with_capacity(n);
+    let mut expected = GroupProjective::<P>::zero();
+    for _ in 0..n {
+        let (p, p_rcb) = ark_point::<P>(input);
+        let s: P::ScalarField = input.scalar();
+        expected += p.mul(s.into_repr());
+        bases.push(p_rcb);
+        scalars.push(s);
+    }
+    let res = arkworks::naive_msm(&bases, &scalars);
+    assert_eq!(homogeneous_form_to_affine(&res), expected.into_affine());
+}
+
+/// Returns the point as halo2curves computes it, and the same point with
+/// its Jacobian coordinates (X : Y : Z) rescaled to (l^2 X : l^3 Y : l Z).
+fn halo2_point<C: halo2::Rcb15Params>(input: &mut Input) -> (C, C) {
+    let flags = input.byte();
+    let k: C::ScalarExt = input.scalar();
+    let l: C::Base = input.scaling();
+
+    let mut native = if flags & FLAG_IDENTITY != 0 {
+        C::identity()
+    } else {
+        C::generator() * k
+    };
+    if flags & FLAG_NEGATE != 0 {
+        native = -native;
+    }
+
+    let (x, y, z) = native.jacobian_coordinates();
+    let scaled = C::new_jacobian(x * l * l, y * l * l * l, z * l).unwrap();
+    (native, scaled)
+}
+
+fn check_halo2_curve<C: halo2::Rcb15Params>(input: &mut Input) {
+    let (a, a_rcb) = halo2_point::<C>(input);
+    let (b, b_rcb) = halo2_point::<C>(input);
+    let s: C::ScalarExt = input.scalar();
+
+    assert_eq!(halo2::add(&a_rcb, &b_rcb), a + b);
+    assert_eq!(halo2::double(&a_rcb), a.double());
+    assert_eq!(halo2::mul(&a_rcb, &s), a * s);
+
+    let n = input.msm_len();
+    let mut bases = Vec::with_capacity(n);
+    let mut scalars = Vec::with_capacity(n);
+    let mut expected = C::identity();
+    for _ in 0..n {
+        let (p, p_rcb) = halo2_point::<C>(input);
+        let s: C::ScalarExt = input.scalar();
+        expected += p * s;
+        bases.push(p_rcb);
+        scalars.push(s);
+    }
+    assert_eq!(halo2::naive_msm(&bases, &scalars), expected);
+}
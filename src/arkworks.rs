@@ -1,35 +1,1050 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use ark_ec::bls12::Bls12Parameters;
 use ark_ec::short_weierstrass_jacobian::GroupAffine;
-use ark_ec::{short_weierstrass_jacobian::GroupProjective, SWModelParameters};
-use ark_ff::PrimeField;
+use ark_ec::{short_weierstrass_jacobian::GroupProjective, ProjectiveCurve, SWModelParameters};
+use ark_ff::{
+    field_new, BigInteger, Field, Fp2, Fp256, Fp256Parameters, Fp2Parameters, Fp384,
+    Fp384Parameters, FpParameters, One, PrimeField, SquareRootField, Zero,
+};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
+use ark_std::rand::distributions::{Distribution, Standard};
+use ark_std::rand::Rng;
+use ark_std::UniformRand;
+use subtle::Choice;
+
+use ark_bls12_377::{Fq as Bls12_377Fq, Fq2 as Bls12_377Fq2, Fr as Bls12_377Fr};
+use ark_bls12_381::{Fq as Bls12_381Fq, Fq2 as Bls12_381Fq2, Fr as Bls12_381Fr};
+use ark_bn254::{Fq as Bn254Fq, Fr as Bn254Fr};
+
+use crate::formulas::{self, Rcb15Field};
+use crate::hash_to_curve::{hash_to_field, map_to_curve, SswuParams};
+#[cfg(feature = "std")]
+use crate::opcount::{self, Formula, OpCount};
+use crate::params::{FormulaClass, SubgroupCheck};
+
+impl<P: Fp256Parameters> Rcb15Field for Fp256<P> {
+    #[inline]
+    fn square(&self) -> Self {
+        Field::square(self)
+    }
+}
+
+impl<P: Fp384Parameters> Rcb15Field for Fp384<P> {
+    #[inline]
+    fn square(&self) -> Self {
+        Field::square(self)
+    }
+}
+
+impl<P: Fp2Parameters> Rcb15Field for Fp2<P> {
+    #[inline]
+    fn square(&self) -> Self {
+        Field::square(self)
+    }
+}
+
+impl<P: Fp384Parameters> crate::hash_to_curve::SswuField for Fp384<P> {
+    fn zero() -> Self {
+        Zero::zero()
+    }
+
+    fn one() -> Self {
+        One::one()
+    }
+
+    fn ct_is_zero(&self) -> Choice {
+        Choice::from(Zero::is_zero(self) as u8)
+    }
+
+    fn ct_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        if choice.into() {
+            *b
+        } else {
+            *a
+        }
+    }
+
+    fn sgn0(&self) -> Choice {
+        Choice::from(self.into_repr().is_odd() as u8)
+    }
+
+    fn from_okm(okm: &[u8]) -> Self {
+        Self::from_be_bytes_mod_order(okm)
+    }
+}
+
+/// Per-curve constants used by the RCB15 formulas.
+///
+/// These are fixed by the curve, so they are computed once here instead of on
+/// every call to `add` or `double`. The cofactor is `SWModelParameters::COFACTOR`.
+pub trait Rcb15Params: SWModelParameters<BaseField: Rcb15Field> {
+    /// `3 * b`, the only curve constant used by the a = 0 formulas.
+    const B3: Self::BaseField;
+    /// Multiply by `B3`. Curves with a small `b` override this with an
+    /// addition chain; the default is a full field multiplication.
+    #[inline]
+    fn mul_by_b3(x: &Self::BaseField) -> Self::BaseField {
+        Self::B3 * *x
+    }
+    /// Which family of RCB15 formulas applies to this curve. Only `AZero` is
+    /// implemented, which the formulas check in debug builds.
+    const FORMULA: FormulaClass;
+    /// A primitive cube root of unity such that `(x, y) -> (beta * x, y)`
+    /// acts as multiplication by `ENDO_LAMBDA` on the prime-order subgroup.
+    const ENDO_BETA: Self::BaseField;
+    /// The eigenvalue of the GLV endomorphism.
+    const ENDO_LAMBDA: Self::ScalarField;
+    /// How membership in the prime-order subgroup should be checked.
+    const SUBGROUP_CHECK: SubgroupCheck;
+    /// Whether a point on the curve is in the prime-order subgroup, checked
+    /// in homogeneous coordinates. The default only knows `CofactorOne` and
+    /// falls back to `[r]P = O`; the BLS12 curves override it with Scott's
+    /// endomorphism tests.
+    fn is_in_subgroup_assuming_on_curve(p: &GroupProjective<Self>) -> bool
+    where
+        Self: Sized,
+    {
+        match Self::SUBGROUP_CHECK {
+            SubgroupCheck::CofactorOne => true,
+            _ => is_in_subgroup_naive(p),
+        }
+    }
+    /// Map a point of the curve into the prime-order subgroup. The default
+    /// multiplies by `COFACTOR`; the BLS12 curves override it with the
+    /// effective cofactors of RFC 9380, which give a different point of the
+    /// subgroup.
+    fn clear_cofactor(p: &GroupProjective<Self>) -> GroupProjective<Self>
+    where
+        Self: Sized,
+    {
+        match Self::SUBGROUP_CHECK {
+            SubgroupCheck::CofactorOne => *p,
+            _ => mul_bits(
+                p,
+                ark_ff::BitIteratorBE::without_leading_zeros(Self::COFACTOR),
+            ),
+        }
+    }
+}
+
+impl Rcb15Params for ark_bn254::g1::Parameters {
+    const B3: Bn254Fq = field_new!(Bn254Fq, "9");
+
+    #[inline]
+    fn mul_by_b3(x: &Bn254Fq) -> Bn254Fq {
+        // 9x = 8x + x
+        x.double().double().double() + x
+    }
+
+    const FORMULA: FormulaClass = FormulaClass::AZero;
+    const ENDO_BETA: Bn254Fq = field_new!(
+        Bn254Fq,
+        "21888242871839275220042445260109153167277707414472061641714758635765020556616"
+    );
+    const ENDO_LAMBDA: Bn254Fr = field_new!(
+        Bn254Fr,
+        "21888242871839275217838484774961031246154997185409878258781734729429964517155"
+    );
+    const SUBGROUP_CHECK: SubgroupCheck = SubgroupCheck::CofactorOne;
+}
+
+impl Rcb15Params for ark_bls12_381::g1::Parameters {
+    const B3: Bls12_381Fq = field_new!(Bls12_381Fq, "12");
+
+    #[inline]
+    fn mul_by_b3(x: &Bls12_381Fq) -> Bls12_381Fq {
+        // 12x = 4 * 3x
+        (x.double() + x).double().double()
+    }
+
+    const FORMULA: FormulaClass = FormulaClass::AZero;
+    const ENDO_BETA: Bls12_381Fq = field_new!(
+        Bls12_381Fq,
+        "793479390729215512621379701633421447060886740281060493010456487427281649075476305620758731620350"
+    );
+    const ENDO_LAMBDA: Bls12_381Fr = field_new!(
+        Bls12_381Fr,
+        "52435875175126190479447740508185965837461563690374988244538805122978187051009"
+    );
+    const SUBGROUP_CHECK: SubgroupCheck = SubgroupCheck::Endomorphism;
+
+    fn is_in_subgroup_assuming_on_curve(p: &GroupProjective<Self>) -> bool {
+        is_in_g1_bls12::<Self>(p, ark_bls12_381::Parameters::X[0])
+    }
+
+    fn clear_cofactor(p: &GroupProjective<Self>) -> GroupProjective<Self> {
+        clear_cofactor_g1_bls12(
+            p,
+            ark_bls12_381::Parameters::X[0],
+            ark_bls12_381::Parameters::X_IS_NEGATIVE,
+        )
+    }
+}
+
+impl Rcb15Params for ark_bls12_377::g1::Parameters {
+    const B3: Bls12_377Fq = field_new!(Bls12_377Fq, "3");
+
+    #[inline]
+    fn mul_by_b3(x: &Bls12_377Fq) -> Bls12_377Fq {
+        // 3x = 2x + x
+        x.double() + x
+    }
+
+    const FORMULA: FormulaClass = FormulaClass::AZero;
+    const ENDO_BETA: Bls12_377Fq = field_new!(
+        Bls12_377Fq,
+        "258664426012969093929703085429980814127835149614277183275038967946009968870203535512256352201271898244626862047231"
+    );
+    const ENDO_LAMBDA: Bls12_377Fr = field_new!(
+        Bls12_377Fr,
+        "8444461749428370424248824938781546531284005582649182570233710176290576793600"
+    );
+    const SUBGROUP_CHECK: SubgroupCheck = SubgroupCheck::Endomorphism;
+
+    fn is_in_subgroup_assuming_on_curve(p: &GroupProjective<Self>) -> bool {
+        is_in_g1_bls12::<Self>(p, ark_bls12_377::Parameters::X[0])
+    }
+
+    fn clear_cofactor(p: &GroupProjective<Self>) -> GroupProjective<Self> {
+        clear_cofactor_g1_bls12(
+            p,
+            ark_bls12_377::Parameters::X[0],
+            ark_bls12_377::Parameters::X_IS_NEGATIVE,
+        )
+    }
+}
+
+impl Rcb15Params for ark_bls12_381::g2::Parameters {
+    const B3: Bls12_381Fq2 = field_new!(
+        Bls12_381Fq2,
+        field_new!(Bls12_381Fq, "12"),
+        field_new!(Bls12_381Fq, "12")
+    );
+
+    #[inline]
+    fn mul_by_b3(x: &Bls12_381Fq2) -> Bls12_381Fq2 {
+        // 12 (1 + u) x = 4 * 3 (c0 - c1 + (c0 + c1) u), as u^2 = -1
+        let t = Bls12_381Fq2::new(x.c0 - x.c1, x.c0 + x.c1);
+        (t.double() + t).double().double()
+    }
+
+    const FORMULA: FormulaClass = FormulaClass::AZero;
+    const ENDO_BETA: Bls12_381Fq2 = field_new!(
+        Bls12_381Fq2,
+        <ark_bls12_381::g1::Parameters as Rcb15Params>::ENDO_BETA,
+        field_new!(Bls12_381Fq, "0")
+    );
+    // beta acts on the twist as the other cube root of unity, x^2 - 1
+    const ENDO_LAMBDA: Bls12_381Fr =
+        field_new!(Bls12_381Fr, "228988810152649578064853576960394133503");
+    const SUBGROUP_CHECK: SubgroupCheck = SubgroupCheck::Frobenius;
+
+    fn is_in_subgroup_assuming_on_curve(p: &GroupProjective<Self>) -> bool {
+        is_in_g2_bls12::<Self, _>(
+            p,
+            BLS12_381_PSI,
+            ark_bls12_381::Parameters::X[0],
+            ark_bls12_381::Parameters::X_IS_NEGATIVE,
+        )
+    }
+
+    fn clear_cofactor(p: &GroupProjective<Self>) -> GroupProjective<Self> {
+        clear_cofactor_g2_bls12::<Self, _>(
+            p,
+            BLS12_381_PSI,
+            ark_bls12_381::Parameters::X[0],
+            ark_bls12_381::Parameters::X_IS_NEGATIVE,
+        )
+    }
+}
+
+impl Rcb15Params for ark_bls12_377::g2::Parameters {
+    const B3: Bls12_377Fq2 = field_new!(
+        Bls12_377Fq2,
+        field_new!(Bls12_377Fq, "0"),
+        field_new!(
+            Bls12_377Fq,
+            "206931540810375275208522186955914826829114810203931728431907410133376374678672658219975110511658688099552257166541"
+        )
+    );
+    const FORMULA: FormulaClass = FormulaClass::AZero;
+    const ENDO_BETA: Bls12_377Fq2 = field_new!(
+        Bls12_377Fq2,
+        <ark_bls12_377::g1::Parameters as Rcb15Params>::ENDO_BETA,
+        field_new!(Bls12_377Fq, "0")
+    );
+    // beta acts on the twist as the other cube root of unity, x^2 - 1
+    const ENDO_LAMBDA: Bls12_377Fr =
+        field_new!(Bls12_377Fr, "91893752504881257701523279626832445440");
+    const SUBGROUP_CHECK: SubgroupCheck = SubgroupCheck::Frobenius;
+
+    fn is_in_subgroup_assuming_on_curve(p: &GroupProjective<Self>) -> bool {
+        is_in_g2_bls12::<Self, _>(
+            p,
+            BLS12_377_PSI,
+            ark_bls12_377::Parameters::X[0],
+            ark_bls12_377::Parameters::X_IS_NEGATIVE,
+        )
+    }
+
+    fn clear_cofactor(p: &GroupProjective<Self>) -> GroupProjective<Self> {
+        clear_cofactor_g2_bls12::<Self, _>(
+            p,
+            BLS12_377_PSI,
+            ark_bls12_377::Parameters::X[0],
+            ark_bls12_377::Parameters::X_IS_NEGATIVE,
+        )
+    }
+}
+
+/// The coefficients of psi on G2 of BLS12-381, an M-type twist with
+/// xi = 1 + u: (xi^-((p - 1) / 3), xi^-((p - 1) / 2)).
+const BLS12_381_PSI: (Bls12_381Fq2, Bls12_381Fq2) = (
+    field_new!(
+        Bls12_381Fq2,
+        field_new!(Bls12_381Fq, "0"),
+        field_new!(
+            Bls12_381Fq,
+            "4002409555221667392624310435006688643935503118305586438271171395842971157480381377015405980053539358417135540939437"
+        )
+    ),
+    field_new!(
+        Bls12_381Fq2,
+        field_new!(
+            Bls12_381Fq,
+            "2973677408986561043442465346520108879172042883009249989176415018091420807192182638567116318576472649347015917690530"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "1028732146235106349975324479215795277384839936929757896155643118032610843298655225875571310552543014690878354869257"
+        )
+    ),
+);
+
+/// The coefficients of psi on G2 of BLS12-377, a D-type twist with xi = u:
+/// (xi^((p - 1) / 3), xi^((p - 1) / 2)).
+const BLS12_377_PSI: (Bls12_377Fq2, Bls12_377Fq2) = (
+    field_new!(
+        Bls12_377Fq2,
+        field_new!(
+            Bls12_377Fq,
+            "80949648264912719408558363140637477264845294720710499478137287262712535938301461879813459410946"
+        ),
+        field_new!(Bls12_377Fq, "0")
+    ),
+    field_new!(
+        Bls12_377Fq2,
+        field_new!(
+            Bls12_377Fq,
+            "216465761340224619389371505802605247630151569547285782856803747159100223055385581585702401816380679166954762214499"
+        ),
+        field_new!(Bls12_377Fq, "0")
+    ),
+);
+
+/// The suite `BLS12381G1_XMD:SHA-256_SSWU_RO_` of RFC 9380, section 8.8.1.
+/// E' and the 11-isogeny are those of appendix E.2; the denominators are the
+/// monic k_(2, i) and k_(4, i) with k_(2, 10) = k_(4, 15) = 1.
+impl SswuParams for ark_bls12_381::g1::Parameters {
+    type Field = Bls12_381Fq;
+    type Hash = sha2::Sha256;
+    const L: usize = 64;
+    const Z: Bls12_381Fq = field_new!(Bls12_381Fq, "11");
+    const ISO_A: Bls12_381Fq = field_new!(
+        Bls12_381Fq,
+        "12190336318893619529228877361869031420615612348429846051986726275283378313155663745811710833465465981901188123677"
+    );
+    const ISO_B: Bls12_381Fq = field_new!(
+        Bls12_381Fq,
+        "2906670324641927570491258158026293881577086121416628140204402091718288198173574630967936031029026176254968826637280"
+    );
+    const ISO_X_NUM: &'static [Bls12_381Fq] = &[
+        field_new!(
+            Bls12_381Fq,
+            "2712959285290305970661081772124144179193819192423276218370281158706191519995889425075952244140278856085036081760695"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "3564859427549639835253027846704205725951033235539816243131874237388832081954622352624080767121604606753339903542203"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "2051387046688339481714726479723076305756384619135044672831882917686431912682625619320120082313093891743187631791280"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "3612713941521031012780325893181011392520079402153354595775735142359240110423346445050803899623018402874731133626465"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "2247053637822768981792833880270996398470828564809439728372634811976089874056583714987807553397615562273407692740057"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "3415427104483187489859740871640064348492611444552862448295571438270821994900526625562705192993481400731539293415811"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "2067521456483432583860405634125513059912765526223015704616050604591207046392807563217109432457129564962571408764292"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "3650721292069012982822225637849018828271936405382082649291891245623305084633066170122780668657208923883092359301262"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "1239271775787030039269460763652455868148971086016832054354147730155061349388626624328773377658494412538595239256855"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "3479374185711034293956731583912244564891370843071137483962415222733470401948838363051960066766720884717833231600798"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "2492756312273161536685660027440158956721981129429869601638362407515627529461742974364729223659746272460004902959995"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "1058488477413994682556770863004536636444795456512795473806825292198091015005841418695586811009326456605062948114985"
+        ),
+    ];
+    const ISO_X_DEN: &'static [Bls12_381Fq] = &[
+        field_new!(
+            Bls12_381Fq,
+            "1353092447850172218905095041059784486169131709710991428415161466575141675351394082965234118340787683181925558786844"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "2822220997908397120956501031591772354860004534930174057793539372552395729721474912921980407622851861692773516917759"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "1717937747208385987946072944131378949849282930538642983149296304709633281382731764122371874602115081850953846504985"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "501624051089734157816582944025690868317536915684467868346388760435016044027032505306995281054569109955275640941784"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "3025903087998593826923738290305187197829899948335370692927241015584233559365859980023579293766193297662657497834014"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "2224140216975189437834161136818943039444741035168992629437640302964164227138031844090123490881551522278632040105125"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "1146414465848284837484508420047674663876992808692209238763293935905506532411661921697047880549716175045414621825594"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "3179090966864399634396993677377903383656908036827452986467581478509513058347781039562481806409014718357094150199902"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "1549317016540628014674302140786462938410429359529923207442151939696344988707002602944342203885692366490121021806145"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "1442797143427491432630626390066422021593505165588630398337491100088557278058060064930663878153124164818522816175370"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "1"
+        ),
+    ];
+    const ISO_Y_NUM: &'static [Bls12_381Fq] = &[
+        field_new!(
+            Bls12_381Fq,
+            "1393399195776646641963150658816615410692049723305861307490980409834842911816308830479576739332720113414154429643571"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "2968610969752762946134106091152102846225411740689724909058016729455736597929366401532929068084731548131227395540630"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "122933100683284845219599644396874530871261396084070222155796123161881094323788483360414289333111221370374027338230"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "303251954782077855462083823228569901064301365507057490567314302006681283228886645653148231378803311079384246777035"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "1353972356724735644398279028378555627591260676383150667237975415318226973994509601413730187583692624416197017403099"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "3443977503653895028417260979421240655844034880950251104724609885224259484262346958661845148165419691583810082940400"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "718493410301850496156792713845282235942975872282052335612908458061560958159410402177452633054233549648465863759602"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "1466864076415884313141727877156167508644960317046160398342634861648153052436926062434809922037623519108138661903145"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "1536886493137106337339531461344158973554574987550750910027365237255347020572858445054025958480906372033954157667719"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "2171468288973248519912068884667133903101171670397991979582205855298465414047741472281361964966463442016062407908400"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "3915937073730221072189646057898966011292434045388986394373682715266664498392389619761133407846638689998746172899634"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "3802409194827407598156407709510350851173404795262202653149767739163117554648574333789388883640862266596657730112910"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "1707589313757812493102695021134258021969283151093981498394095062397393499601961942449581422761005023512037430861560"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "349697005987545415860583335313370109325490073856352967581197273584891698473628451945217286148025358795756956811571"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "885704436476567581377743161796735879083481447641210566405057346859953524538988296201011389016649354976986251207243"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "3370924952219000111210625390420697640496067348723987858345031683392215988129398381698161406651860675722373763741188"
+        ),
+    ];
+    const ISO_Y_DEN: &'static [Bls12_381Fq] = &[
+        field_new!(
+            Bls12_381Fq,
+            "3396434800020507717552209507749485772788165484415495716688989613875369612529138640646200921379825018840894888371137"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "3907278185868397906991868466757978732688957419873771881240086730384895060595583602347317992689443299391009456758845"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "854914566454823955479427412036002165304466268547334760894270240966182605542146252771872707010378658178126128834546"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "3496628876382137961119423566187258795236027183112131017519536056628828830323846696121917502443333849318934945158166"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "1828256966233331991927609917644344011503610008134915752990581590799656305331275863706710232159635159092657073225757"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "1362317127649143894542621413133849052553333099883364300946623208643344298804722863920546222860227051989127113848748"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "3443845896188810583748698342858554856823966611538932245284665132724280883115455093457486044009395063504744802318172"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "3484671274283470572728732863557945897902920439975203610275006103818288159899345245633896492713412187296754791689945"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "3755735109429418587065437067067640634211015783636675372165599470771975919172394156249639331555277748466603540045130"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "3459661102222301807083870307127272890283709299202626530836335779816726101522661683404130556379097384249447658110805"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "742483168411032072323733249644347333168432665415341249073150659015707795549260947228694495111018381111866512337576"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "1662231279858095762833829698537304807741442669992646287950513237989158777254081548205552083108208170765474149568658"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "1668238650112823419388205992952852912407572045257706138925379268508860023191233729074751042562151098884528280913356"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "369162719928976119195087327055926326601627748362769544198813069133429557026740823593067700396825489145575282378487"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "2164195715141237148945939585099633032390257748382945597506236650132835917087090097395995817229686247227784224263055"
+        ),
+        field_new!(
+            Bls12_381Fq,
+            "1"
+        ),
+    ];
+
+    fn sqrt_ratio(u: &Bls12_381Fq, v: &Bls12_381Fq) -> (Choice, Bls12_381Fq) {
+        sqrt_ratio(u, v, &Self::Z)
+    }
+}
+
+/// The homogeneous identity is (0 : 1 : 0).
+pub fn homogeneous_identity<P: SWModelParameters>() -> GroupProjective<P> {
+    GroupProjective::<P>::new(
+        P::BaseField::zero(),
+        P::BaseField::one(),
+        P::BaseField::zero(),
+    )
+}
+
+/// Affine (x, y) is (x : y : 1); the point at infinity maps to (0 : 1 : 0).
+pub fn affine_to_homogeneous_form<P: SWModelParameters>(p: &GroupAffine<P>) -> GroupProjective<P> {
+    if p.infinity {
+        return homogeneous_identity();
+    }
+    GroupProjective::<P>::new(p.x, p.y, P::BaseField::one())
+}
 
 pub fn homogeneous_form_to_affine<P: SWModelParameters>(x: &GroupProjective<P>) -> GroupAffine<P> {
+    if x.z.is_zero() {
+        return GroupAffine::<P>::zero();
+    }
     GroupAffine::<P>::new(x.x / x.z, x.y / x.z, false)
 }
 
-pub fn add<P: SWModelParameters>(
+/// Jacobian (X, Y, Z) is the affine point (X / Z^2, Y / Z^3), i.e. the
+/// homogeneous (X Z : Y : Z^3). This lets the native arkworks points be
+/// compared with [`homogeneous_eq`] without normalising either side.
+pub fn jacobian_to_homogeneous<P: SWModelParameters>(p: &GroupProjective<P>) -> GroupProjective<P> {
+    GroupProjective::<P>::new(p.x * p.z, p.y, p.z.square() * p.z)
+}
+
+/// A point in homogeneous coordinates.
+///
+/// The functions of this module use `GroupProjective` as a container for
+/// (X : Y : Z); this wrapper marks such a value as homogeneous, so that it can
+/// carry its own encodings without being mistaken for a Jacobian point.
+pub struct HomogeneousPoint<P: Rcb15Params>(pub GroupProjective<P>);
+
+impl<P: Rcb15Params> Clone for HomogeneousPoint<P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P: Rcb15Params> Copy for HomogeneousPoint<P> {}
+
+impl<P: Rcb15Params> fmt::Debug for HomogeneousPoint<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("HomogeneousPoint")
+            .field(&self.0.x)
+            .field(&self.0.y)
+            .field(&self.0.z)
+            .finish()
+    }
+}
+
+impl<P: Rcb15Params> HomogeneousPoint<P> {
+    pub fn identity() -> Self {
+        Self(homogeneous_identity())
+    }
+
+    pub fn from_affine(p: &GroupAffine<P>) -> Self {
+        Self(affine_to_homogeneous_form(p))
+    }
+
+    pub fn to_affine(&self) -> GroupAffine<P> {
+        homogeneous_form_to_affine(&self.0)
+    }
+
+    /// A uniform point of the prime-order subgroup, like `UniformRand::rand`,
+    /// but scaled by a random non-zero factor so that Z is not one.
+    pub fn rand_with_random_z<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let mut p = Self::rand(rng);
+        p.randomize_representation(rng);
+        p
+    }
+
+    /// Replaces (X : Y : Z) by (λX : λY : λZ) for a random non-zero λ. The
+    /// point is unchanged, but its representation is not predictable.
+    pub fn randomize_representation<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let lambda = loop {
+            let lambda = P::BaseField::rand(rng);
+            if !lambda.is_zero() {
+                break lambda;
+            }
+        };
+        self.0.x *= lambda;
+        self.0.y *= lambda;
+        self.0.z *= lambda;
+    }
+
+    fn read_coordinates<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let x = P::BaseField::deserialize(&mut reader)?;
+        let y = P::BaseField::deserialize(&mut reader)?;
+        let z = P::BaseField::deserialize(&mut reader)?;
+        Ok(Self(GroupProjective::new(x, y, z)))
+    }
+}
+
+impl<P: Rcb15Params> PartialEq for HomogeneousPoint<P> {
+    fn eq(&self, other: &Self) -> bool {
+        homogeneous_eq(&self.0, &other.0)
+    }
+}
+
+impl<P: Rcb15Params> Eq for HomogeneousPoint<P> {}
+
+/// `UniformRand` for [`HomogeneousPoint`]: a uniform point of the
+/// prime-order subgroup, normalised to Z = 1.
+impl<P: Rcb15Params> Distribution<HomogeneousPoint<P>> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> HomogeneousPoint<P> {
+        HomogeneousPoint::from_affine(&GroupProjective::<P>::rand(rng).into_affine())
+    }
+}
+
+/// `serialize` writes the normalised affine point in the compressed encoding
+/// of arkworks. `serialize_uncompressed` writes X, Y and Z as they are, so an
+/// accumulator can be stored without an inversion and read back unchanged.
+impl<P: Rcb15Params> CanonicalSerialize for HomogeneousPoint<P> {
+    fn serialize<W: Write>(&self, writer: W) -> Result<(), SerializationError> {
+        self.to_affine().serialize(writer)
+    }
+
+    fn serialized_size(&self) -> usize {
+        GroupAffine::<P>::zero().serialized_size()
+    }
+
+    fn serialize_uncompressed<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        self.0.x.serialize(&mut writer)?;
+        self.0.y.serialize(&mut writer)?;
+        self.0.z.serialize(&mut writer)
+    }
+
+    fn uncompressed_size(&self) -> usize {
+        3 * self.0.x.serialized_size()
+    }
+}
+
+/// Both encodings are checked to be on the curve and in the prime-order
+/// subgroup; `deserialize_unchecked` reads the uncompressed encoding
+/// without any check.
+impl<P: Rcb15Params> CanonicalDeserialize for HomogeneousPoint<P> {
+    fn deserialize<R: Read>(reader: R) -> Result<Self, SerializationError> {
+        GroupAffine::<P>::deserialize(reader).map(|p| Self::from_affine(&p))
+    }
+
+    fn deserialize_uncompressed<R: Read>(reader: R) -> Result<Self, SerializationError> {
+        let p = Self::read_coordinates(reader)?;
+        if !is_in_subgroup(&p.0) {
+            return Err(SerializationError::InvalidData);
+        }
+        Ok(p)
+    }
+
+    fn deserialize_unchecked<R: Read>(reader: R) -> Result<Self, SerializationError> {
+        Self::read_coordinates(reader)
+    }
+}
+
+fn satisfies_curve_equation<P: Rcb15Params>(p: &GroupProjective<P>) -> bool {
+    let (lhs, rhs) = formulas::curve_equation(p.x, p.y, p.z, P::mul_by_b3);
+    lhs == rhs
+}
+
+fn is_zero_triple<P: SWModelParameters>(p: &GroupProjective<P>) -> bool {
+    p.x.is_zero() & p.y.is_zero() & p.z.is_zero()
+}
+
+/// Whether (X : Y : Z) is a point of the curve, i.e. Y^2 Z = X^3 + b Z^3 and
+/// not (0 : 0 : 0), checked without an inversion. The identity (0 : 1 : 0)
+/// is on the curve.
+pub fn is_on_curve_homogeneous<P: Rcb15Params>(p: &GroupProjective<P>) -> bool {
+    satisfies_curve_equation(p) && !is_zero_triple(p)
+}
+
+/// [`is_on_curve_homogeneous`] for any short Weierstrass curve, i.e.
+/// Y^2 Z = X^3 + a X Z^2 + b Z^3.
+pub fn is_on_curve_homogeneous_generic<P: SWModelParameters>(p: &GroupProjective<P>) -> bool {
+    let z2 = p.z.square();
+    let lhs = p.y.square() * p.z;
+    let rhs = p.x.square() * p.x + P::COEFF_A * p.x * z2 + P::COEFF_B * z2 * p.z;
+    lhs == rhs && !is_zero_triple(p)
+}
+
+/// Whether two homogeneous triples are the same point, i.e. X1 Z2 = X2 Z1 and
+/// Y1 Z2 = Y2 Z1, without an inversion. Both are the identity iff both Z are
+/// zero. `GroupProjective`'s own `==` assumes Jacobian coordinates and must
+/// not be used on these triples.
+pub fn homogeneous_eq<P: SWModelParameters>(
     p1: &GroupProjective<P>,
     p2: &GroupProjective<P>,
+) -> bool {
+    match (p1.z.is_zero(), p2.z.is_zero()) {
+        (true, true) => true,
+        (false, false) => p1.x * p2.z == p2.x * p1.z && p1.y * p2.z == p2.y * p1.z,
+        _ => false,
+    }
+}
+
+/// Whether (X : Y : Z) is on the curve and in the prime-order subgroup,
+/// without normalising it first.
+pub fn is_in_subgroup<P: Rcb15Params>(p: &GroupProjective<P>) -> bool {
+    is_on_curve_homogeneous(p) && P::is_in_subgroup_assuming_on_curve(p)
+}
+
+/// `[r]P = O` for a point on the curve, with the RCB15 `mul`. `r` is zero as
+/// a scalar, so this checks `[r - 1]P = -P` instead.
+pub fn is_in_subgroup_naive<P: Rcb15Params>(p: &GroupProjective<P>) -> bool {
+    homogeneous_eq(&mul(p, &-P::ScalarField::one()), &neg(p))
+}
+
+/// Scott's test for G1 of a BLS12 curve with parameter x (eprint:2021-1130):
+/// P is in the subgroup iff phi(P) = [-x^2]P, where
+/// phi(X : Y : Z) = (beta X : Y : Z) has eigenvalue `ENDO_LAMBDA` = -x^2.
+fn is_in_g1_bls12<P: Rcb15Params>(p: &GroupProjective<P>, x: u64) -> bool {
+    let phi = GroupProjective::<P>::new(p.x * P::ENDO_BETA, p.y, p.z);
+    homogeneous_eq(&phi, &neg(&mul_u64(&mul_u64(p, x), x)))
+}
+
+/// Scott's test for G2 of a BLS12 curve with parameter x (eprint:2021-1130):
+/// P is in the subgroup iff psi(P) = [x]P.
+fn is_in_g2_bls12<P, Q>(
+    p: &GroupProjective<P>,
+    psi: (Fp2<Q>, Fp2<Q>),
+    x: u64,
+    x_is_negative: bool,
+) -> bool
+where
+    P: Rcb15Params<BaseField = Fp2<Q>>,
+    Q: Fp2Parameters,
+{
+    homogeneous_eq(&psi_bls12(p, psi), &mul_by_x(p, x, x_is_negative))
+}
+
+/// Cofactor clearing for G1 of a BLS12 curve: multiplication by
+/// h_eff = 1 - x, which maps every point of E(Fq) into G1 (RFC 9380,
+/// section 7). On BLS12-377, whose G1 curve has points of order two, a point
+/// with no component in G1 can hit an exceptional case of the formulas and
+/// give (0 : 0 : 0); the result is then the identity, which is mapped to
+/// (0 : 1 : 0) without a branch.
+fn clear_cofactor_g1_bls12<P: Rcb15Params>(
+    p: &GroupProjective<P>,
+    x: u64,
+    x_is_negative: bool,
+) -> GroupProjective<P> {
+    let res = add(p, &neg(&mul_by_x(p, x, x_is_negative)));
+    let y = res.y + P::BaseField::from(is_zero_triple(&res));
+    GroupProjective::new(res.x, y, res.z)
+}
+
+/// Cofactor clearing for G2 of a BLS12 curve, after Budroni and Pintore
+/// (eprint:2017-419): h_eff P = [x^2 - x - 1]P + [x - 1]psi(P) + psi^2(2P),
+/// with the addition chain of RFC 9380, appendix G.3. h_eff is
+/// 3 (x^2 - 1) times the cofactor.
+fn clear_cofactor_g2_bls12<P, Q>(
+    p: &GroupProjective<P>,
+    psi: (Fp2<Q>, Fp2<Q>),
+    x: u64,
+    x_is_negative: bool,
+) -> GroupProjective<P>
+where
+    P: Rcb15Params<BaseField = Fp2<Q>>,
+    Q: Fp2Parameters,
+{
+    let t1 = mul_by_x(p, x, x_is_negative);
+    let t2 = psi_bls12(p, psi);
+    let t3 = psi_bls12(&psi_bls12(&double(p), psi), psi);
+    let t3 = add(&t3, &neg(&t2));
+    let t2 = mul_by_x(&add(&t1, &t2), x, x_is_negative);
+    let t3 = add(&add(&t3, &t2), &neg(&t1));
+    add(&t3, &neg(p))
+}
+
+/// The untwist-Frobenius-twist endomorphism on G2 of a BLS12 curve,
+/// psi(X : Y : Z) = (cx conj(X) : cy conj(Y) : conj(Z)).
+fn psi_bls12<P, Q>(p: &GroupProjective<P>, (cx, cy): (Fp2<Q>, Fp2<Q>)) -> GroupProjective<P>
+where
+    P: Rcb15Params<BaseField = Fp2<Q>>,
+    Q: Fp2Parameters,
+{
+    let conjugate = |mut a: Fp2<Q>| {
+        a.conjugate();
+        a
+    };
+    GroupProjective::<P>::new(cx * conjugate(p.x), cy * conjugate(p.y), conjugate(p.z))
+}
+
+/// Multiplication by the signed parameter x of a BLS12 curve.
+fn mul_by_x<P: Rcb15Params>(
+    p: &GroupProjective<P>,
+    x: u64,
+    x_is_negative: bool,
 ) -> GroupProjective<P> {
-    let b3 = P::BaseField::from(3u64) * P::COEFF_B;
-    let (x3, y3, z3) = core_add::<P>(p1.x, p1.y, p1.z, p2.x, p2.y, p2.z, b3);
+    let xp = mul_u64(p, x);
+    if x_is_negative {
+        neg(&xp)
+    } else {
+        xp
+    }
+}
+
+fn neg<P: SWModelParameters>(p: &GroupProjective<P>) -> GroupProjective<P> {
+    GroupProjective::<P>::new(p.x, -p.y, p.z)
+}
+
+/// The formulas map solutions of the curve equation to solutions. The result
+/// can still be (0 : 0 : 0) when an input has order two, so that is not
+/// checked here.
+fn debug_check<P: Rcb15Params>(inputs: &[&GroupProjective<P>], res: &GroupProjective<P>) {
+    debug_assert_eq!(P::FORMULA, FormulaClass::AZero);
+    debug_assert!(
+        !inputs.iter().all(|p| satisfies_curve_equation(p)) || satisfies_curve_equation(res),
+        "the result of an RCB15 formula is not on the curve"
+    );
+}
+
+pub fn add<P: Rcb15Params>(p1: &GroupProjective<P>, p2: &GroupProjective<P>) -> GroupProjective<P> {
+    let (x3, y3, z3) = formulas::add(p1.x, p1.y, p1.z, p2.x, p2.y, p2.z, P::mul_by_b3);
+
+    let res = GroupProjective::<P>::new(x3, y3, z3);
+    debug_check(&[p1, p2], &res);
+    res
+}
 
-    GroupProjective::<P>::new(x3, y3, z3)
+pub fn double<P: Rcb15Params>(p: &GroupProjective<P>) -> GroupProjective<P> {
+    let (x3, y3, z3) = formulas::double(p.x, p.y, p.z, P::mul_by_b3);
+
+    let res = GroupProjective::<P>::new(x3, y3, z3);
+    debug_check(&[p], &res);
+    res
 }
 
-pub fn double<P: SWModelParameters>(p: &GroupProjective<P>) -> GroupProjective<P> {
-    let b3 = P::BaseField::from(3u64) * P::COEFF_B;
-    let (x3, y3, z3) = core_double::<P>(p.x, p.y, p.z, b3);
+/// Mixed addition of a homogeneous point and an affine point, i.e. the
+/// second operand has Z = 1. `p2` may not be the point at infinity in the
+/// formula itself, so that case is handled separately.
+pub fn add_mixed<P: Rcb15Params>(
+    p1: &GroupProjective<P>,
+    p2: &GroupAffine<P>,
+) -> GroupProjective<P> {
+    if p2.infinity {
+        return *p1;
+    }
+    let (x3, y3, z3) = formulas::add_mixed(p1.x, p1.y, p1.z, p2.x, p2.y, P::mul_by_b3);
 
-    GroupProjective::<P>::new(x3, y3, z3)
+    let res = GroupProjective::<P>::new(x3, y3, z3);
+    debug_check(&[p1, &affine_to_homogeneous_form(p2)], &res);
+    res
+}
+
+/// Field-operation counts of `formula` on this curve.
+#[cfg(feature = "std")]
+pub fn op_count<P: Rcb15Params>(formula: Formula) -> OpCount {
+    opcount::count(formula, P::BaseField::one(), P::mul_by_b3)
 }
 
 /// Naive double-then-add method for group multiplications.
-pub fn mul<P: SWModelParameters>(
+pub fn mul<P: Rcb15Params>(
+    base: &GroupProjective<P>,
+    scalar: &P::ScalarField,
+) -> GroupProjective<P> {
+    mul_bits(
+        base,
+        ark_ff::BitIteratorBE::without_leading_zeros(scalar.into_repr()),
+    )
+}
+
+/// `mul` with a blinded scalar: the point is (k + m r) P for a random 64-bit
+/// m, where r is the order of the scalar field, so that the bits processed
+/// differ on every call. This is kP for P in the prime-order subgroup; the
+/// formulas are complete, so the extra bits cannot reach an exceptional case.
+/// Every bit of the blinded scalar, leading zeros included, costs a doubling,
+/// an addition and a selection, so its length and weight do not change the
+/// sequence of operations.
+pub fn mul_blinded<P: Rcb15Params, R: Rng + ?Sized>(
     base: &GroupProjective<P>,
     scalar: &P::ScalarField,
+    rng: &mut R,
+) -> GroupProjective<P> {
+    let m = rng.gen::<u64>() as u128;
+    let k = scalar.into_repr();
+    let r = <P::ScalarField as PrimeField>::Params::MODULUS;
+
+    let mut limbs = Vec::with_capacity(k.as_ref().len() + 1);
+    let mut carry = 0u128;
+    for (k_i, r_i) in k.as_ref().iter().zip(r.as_ref()) {
+        let acc = *k_i as u128 + m * *r_i as u128 + carry;
+        limbs.push(acc as u64);
+        carry = acc >> 64;
+    }
+    limbs.push(carry as u64);
+    mul_ladder(homogeneous_identity(), base, &limbs)
+}
+
+/// Scalar multiplication on a fixed-length ladder over every bit of the
+/// scalar, with randomised representations: the accumulator starts at
+/// (0 : λ : 0) and the base is scaled by an independent random factor, so no
+/// intermediate triple is predictable from the inputs. The result is the
+/// same point as `mul`.
+pub fn mul_randomized<P: Rcb15Params, R: Rng + ?Sized>(
+    base: &HomogeneousPoint<P>,
+    scalar: &P::ScalarField,
+    rng: &mut R,
+) -> HomogeneousPoint<P> {
+    let mut start = HomogeneousPoint::identity();
+    start.randomize_representation(rng);
+    let mut base = *base;
+    base.randomize_representation(rng);
+    HomogeneousPoint(mul_ladder(start.0, &base.0, scalar.into_repr().as_ref()))
+}
+
+/// Scalar multiplication with a fixed sequence of operations: every bit of
+/// the little-endian `limbs`, leading zeros included, costs a doubling, an
+/// addition and a selection. The formulas are complete, so the identity
+/// needs no branch.
+///
+/// The arkworks fields have no conditional selection, so it is arithmetic,
+/// `a + bit (b - a)`. This keeps the sequence of formulas independent of the
+/// scalar; the arkworks field arithmetic itself is not constant time.
+fn mul_ladder<P: Rcb15Params>(
+    start: GroupProjective<P>,
+    base: &GroupProjective<P>,
+    limbs: &[u64],
+) -> GroupProjective<P> {
+    let select = |a: P::BaseField, b: P::BaseField, bit: P::BaseField| a + bit * (b - a);
+    let mut res = start;
+    for limb in limbs.iter().rev() {
+        for i in (0..64).rev() {
+            res = double(&res);
+            let sum = add(&res, base);
+            let bit = P::BaseField::from((limb >> i) & 1);
+            res = GroupProjective::new(
+                select(res.x, sum.x, bit),
+                select(res.y, sum.y, bit),
+                select(res.z, sum.z, bit),
+            );
+        }
+    }
+    res
+}
+
+/// `mul` by a small integer, e.g. the parameter of a BLS12 curve.
+fn mul_u64<P: Rcb15Params>(base: &GroupProjective<P>, k: u64) -> GroupProjective<P> {
+    mul_bits(base, ark_ff::BitIteratorBE::without_leading_zeros([k]))
+}
+
+fn mul_bits<P: Rcb15Params>(
+    base: &GroupProjective<P>,
+    bits: impl Iterator<Item = bool>,
 ) -> GroupProjective<P> {
     let mut res = None;
-    for b in ark_ff::BitIteratorBE::without_leading_zeros(scalar.into_repr()) {
+    for b in bits {
         if res.is_some() {
             res = Some(double(&res.unwrap()));
         }
@@ -41,11 +1056,42 @@ pub fn mul<P: SWModelParameters>(
             }
         }
     }
-    res.unwrap()
+    // a zero scalar has no set bits
+    res.unwrap_or_else(homogeneous_identity)
+}
+
+/// `sqrt_ratio` of RFC 9380 by a division and square roots. Unlike the
+/// halo2 backend, the arkworks fields are not constant time anyway.
+fn sqrt_ratio<F: SquareRootField>(u: &F, v: &F, z: &F) -> (Choice, F) {
+    let ratio = *u * v.inverse().unwrap_or_else(F::zero);
+    match ratio.sqrt() {
+        Some(y) => (Choice::from(1), y),
+        None => (
+            Choice::from(0),
+            (*z * ratio).sqrt().expect("Z is not a square"),
+        ),
+    }
+}
+
+/// `hash_to_curve` of RFC 9380 with the suite of `P`, e.g.
+/// `BLS12381G1_XMD:SHA-256_SSWU_RO_` for BLS12-381 G1. The two outputs of
+/// `map_to_curve` are added with the RCB15 formulas and the cofactor is
+/// cleared with [`Rcb15Params::clear_cofactor`]. The arguments are in the
+/// order of `halo2::hash_to_curve`, domain separation first.
+pub fn hash_to_curve<P>(dst: &[u8], msg: &[u8]) -> HomogeneousPoint<P>
+where
+    P: Rcb15Params + SswuParams<Field = <P as ark_ec::ModelParameters>::BaseField>,
+{
+    let [u0, u1] = hash_to_field::<P>(msg, dst);
+    let (x0, y0, z0) = map_to_curve::<P>(&u0);
+    let (x1, y1, z1) = map_to_curve::<P>(&u1);
+    let q0 = GroupProjective::<P>::new(x0, y0, z0);
+    let q1 = GroupProjective::<P>::new(x1, y1, z1);
+    HomogeneousPoint(P::clear_cofactor(&add(&q0, &q1)))
 }
 
 /// Naive msm that does the sum of product without any optimizations.
-pub fn naive_msm<P: SWModelParameters>(
+pub fn naive_msm<P: Rcb15Params>(
     points: &[GroupProjective<P>],
     scalars: &[P::ScalarField],
 ) -> GroupProjective<P> {
@@ -56,98 +1102,3 @@ pub fn naive_msm<P: SWModelParameters>(
     }
     res
 }
-
-fn core_add<P: SWModelParameters>(
-    x1: P::BaseField,
-    y1: P::BaseField,
-    z1: P::BaseField,
-    x2: P::BaseField,
-    y2: P::BaseField,
-    z2: P::BaseField,
-    b3: P::BaseField,
-) -> (P::BaseField, P::BaseField, P::BaseField) {
-    // Algorithm 7 of eprint:2015-1060
-    // Source code from A.3
-    let t0_1 = x1 * x2; // mul #1
-    let t1_2 = y1 * y2; // mul #2
-    let t2_3 = z1 * z2; // mul #3
-
-    let t3_4 = x1 + y1;
-    let t4_5 = x2 + y2;
-    let t3_6 = t3_4 * t4_5; // mul #4
-
-    let t4_7 = t0_1 + t1_2;
-    let t3_8 = t3_6 - t4_7;
-    let t4_9 = y1 + z1;
-
-    let x3_10 = y2 + z2;
-    let t4_11 = t4_9 * x3_10; // mul #5
-    let x3_12 = t1_2 + t2_3;
-
-    let t4_13 = t4_11 - x3_12;
-    let x3_14 = x1 + z1;
-    let y3_15 = x2 + z2;
-
-    let x3_16 = x3_14 * y3_15; // mul #6
-    let y3_17 = t0_1 + t2_3;
-    let y3_18 = x3_16 - y3_17;
-
-    let x3_19 = t0_1 + t0_1;
-    let t0_20 = x3_19 + t0_1;
-    // b3 is a constant -- so this multiplication is cheap
-    let t2_21 = b3 * t2_3;
-
-    let z3_22 = t1_2 + t2_21;
-    let t1_23 = t1_2 - t2_21;
-    // b3 is a constant -- so this multiplication is cheap
-    let y3_24 = b3 * y3_18;
-
-    let x3_25 = t4_13 * y3_24; // mul #7
-    let t2_26 = t3_8 * t1_23; // mul #8
-    let x3_27 = t2_26 - x3_25;
-
-    let y3_28 = y3_24 * t0_20; // mul #9
-    let t1_29 = t1_23 * z3_22; // mul #10
-    let y3_30 = t1_29 + y3_28;
-
-    let t0_31 = t0_20 * t3_8; // mul #11
-    let z3_32 = z3_22 * t4_13; // mul #12
-    let z3_33 = z3_32 + t0_31;
-
-    (x3_27, y3_30, z3_33)
-}
-
-fn core_double<P: SWModelParameters>(
-    x: P::BaseField,
-    y: P::BaseField,
-    z: P::BaseField,
-    b3: P::BaseField,
-) -> (P::BaseField, P::BaseField, P::BaseField) {
-    // Algorithm 7 of eprint:2015-1060
-    // Source code from A.3
-    let t0_1 = y * y;
-    let z3_2 = t0_1 + t0_1;
-    let z3_3 = z3_2 + z3_2;
-
-    let z3_4 = z3_3 + z3_3;
-    let t1_5 = y * z;
-    let t2_6 = z * z;
-
-    let t2_7 = b3 * t2_6;
-    let x3_8 = t2_7 * z3_4;
-    let y3_9 = t0_1 + t2_7;
-
-    let z3_10 = t1_5 * z3_4;
-    let t1_11 = t2_7 + t2_7;
-    let t2_12 = t1_11 + t2_7;
-
-    let t0_13 = t0_1 - t2_12;
-    let y3_14 = t0_13 * y3_9;
-    let y3_15 = x3_8 + y3_14;
-
-    let t1_16 = x * y;
-    let x3_17 = t0_13 * t1_16;
-    let x3_18 = x3_17 + x3_17;
-
-    (x3_18, y3_15, z3_10)
-}
@@ -0,0 +1,60 @@
+//! Conversions between the arkworks and halo2curves implementations of BN254.
+//!
+//! Field elements are moved through their canonical little-endian byte
+//! encodings, which are identical in both libraries; points are moved
+//! coordinate by coordinate.
+
+use ark_ff::Zero;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use halo2curves::bn256;
+use halo2curves::group::ff::PrimeField;
+use halo2curves::group::prime::PrimeCurveAffine;
+use halo2curves::CurveAffine;
+
+fn ark_to_halo2_field<A: CanonicalSerialize, H: PrimeField>(x: &A) -> H {
+    let mut repr = H::Repr::default();
+    x.serialize(repr.as_mut()).unwrap();
+    H::from_repr(repr).unwrap()
+}
+
+fn halo2_to_ark_field<H: PrimeField, A: CanonicalDeserialize>(x: &H) -> A {
+    A::deserialize(x.to_repr().as_ref()).unwrap()
+}
+
+pub fn fq_ark_to_halo2(x: &ark_bn254::Fq) -> bn256::Fq {
+    ark_to_halo2_field(x)
+}
+
+pub fn fq_halo2_to_ark(x: &bn256::Fq) -> ark_bn254::Fq {
+    halo2_to_ark_field(x)
+}
+
+pub fn fr_ark_to_halo2(x: &ark_bn254::Fr) -> bn256::Fr {
+    ark_to_halo2_field(x)
+}
+
+pub fn fr_halo2_to_ark(x: &bn256::Fr) -> ark_bn254::Fr {
+    halo2_to_ark_field(x)
+}
+
+pub fn g1_ark_to_halo2(p: &ark_bn254::G1Affine) -> bn256::G1Affine {
+    if p.is_zero() {
+        return bn256::G1Affine::identity();
+    }
+    bn256::G1Affine::from_xy(fq_ark_to_halo2(&p.x), fq_ark_to_halo2(&p.y)).unwrap()
+}
+
+pub fn g1_halo2_to_ark(p: &bn256::G1Affine) -> ark_bn254::G1Affine {
+    let coordinates = p.coordinates();
+    if coordinates.is_none().into() {
+        return ark_bn254::G1Affine::zero();
+    }
+    let coordinates = coordinates.unwrap();
+    let p = ark_bn254::G1Affine::new(
+        fq_halo2_to_ark(coordinates.x()),
+        fq_halo2_to_ark(coordinates.y()),
+        false,
+    );
+    assert!(p.is_on_curve());
+    p
+}
@@ -0,0 +1,160 @@
+//! The a = 0 formulas of RCB15 in homogeneous projective coordinates.
+//!
+//! They are written once, as straight-line programs (see [`crate::ir`]), so
+//! that both backends share the same code and the formulas can also be run
+//! over instrumented fields (see [`crate::opcount`]). Multiplication by `3b`
+//! is passed in by the caller, since it depends on the curve rather than on
+//! the field.
+
+use core::ops::{Add, Mul, Sub};
+
+use crate::ir::straight_line;
+
+/// The field operations used by the formulas.
+pub trait Rcb15Field: Copy + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> {
+    fn square(&self) -> Self;
+}
+
+straight_line! {
+    /// Complete addition, Algorithm 7 of eprint:2015-1060 (source code
+    /// from A.3).
+    pub fn add, const ADD = (x1, y1, z1, x2, y2, z2) -> (x3_27, y3_30, z3_33) {
+        t0_1 = mul(x1, x2);
+        t1_2 = mul(y1, y2);
+        t2_3 = mul(z1, z2);
+
+        t3_4 = add(x1, y1);
+        t4_5 = add(x2, y2);
+        t3_6 = mul(t3_4, t4_5);
+
+        t4_7 = add(t0_1, t1_2);
+        t3_8 = sub(t3_6, t4_7);
+        t4_9 = add(y1, z1);
+
+        x3_10 = add(y2, z2);
+        t4_11 = mul(t4_9, x3_10);
+        x3_12 = add(t1_2, t2_3);
+
+        t4_13 = sub(t4_11, x3_12);
+        x3_14 = add(x1, z1);
+        y3_15 = add(x2, z2);
+
+        x3_16 = mul(x3_14, y3_15);
+        y3_17 = add(t0_1, t2_3);
+        y3_18 = sub(x3_16, y3_17);
+
+        x3_19 = add(t0_1, t0_1);
+        t0_20 = add(x3_19, t0_1);
+        t2_21 = mul_b3(t2_3);
+
+        z3_22 = add(t1_2, t2_21);
+        t1_23 = sub(t1_2, t2_21);
+        y3_24 = mul_b3(y3_18);
+
+        x3_25 = mul(t4_13, y3_24);
+        t2_26 = mul(t3_8, t1_23);
+        x3_27 = sub(t2_26, x3_25);
+
+        y3_28 = mul(y3_24, t0_20);
+        t1_29 = mul(t1_23, z3_22);
+        y3_30 = add(t1_29, y3_28);
+
+        t0_31 = mul(t0_20, t3_8);
+        z3_32 = mul(z3_22, t4_13);
+        z3_33 = add(z3_32, t0_31);
+    }
+}
+
+straight_line! {
+    /// Mixed addition with Z2 = 1, Algorithm 8 of eprint:2015-1060 (source
+    /// code from A.3). The second operand cannot be the identity.
+    pub fn add_mixed, const ADD_MIXED = (x1, y1, z1, x2, y2) -> (x3_20, y3_23, z3_26) {
+        t0_1 = mul(x1, x2);
+        t1_2 = mul(y1, y2);
+        t3_3 = add(x2, y2);
+
+        t4_4 = add(x1, y1);
+        t3_5 = mul(t3_3, t4_4);
+        t4_6 = add(t0_1, t1_2);
+
+        t3_7 = sub(t3_5, t4_6);
+        t4_8 = mul(y2, z1);
+        t4_9 = add(t4_8, y1);
+
+        y3_10 = mul(x2, z1);
+        y3_11 = add(y3_10, x1);
+        x3_12 = add(t0_1, t0_1);
+
+        t0_13 = add(x3_12, t0_1);
+        t2_14 = mul_b3(z1);
+        z3_15 = add(t1_2, t2_14);
+
+        t1_16 = sub(t1_2, t2_14);
+        y3_17 = mul_b3(y3_11);
+        x3_18 = mul(t4_9, y3_17);
+
+        t2_19 = mul(t3_7, t1_16);
+        x3_20 = sub(t2_19, x3_18);
+        y3_21 = mul(y3_17, t0_13);
+
+        t1_22 = mul(t1_16, z3_15);
+        y3_23 = add(t1_22, y3_21);
+        t0_24 = mul(t0_13, t3_7);
+
+        z3_25 = mul(z3_15, t4_9);
+        z3_26 = add(z3_25, t0_24);
+    }
+}
+
+straight_line! {
+    /// Doubling, Algorithm 9 of eprint:2015-1060 (source code from A.3).
+    pub fn double, const DOUBLE = (x, y, z) -> (x3_18, y3_15, z3_10) {
+        t0_1 = square(y);
+        z3_2 = add(t0_1, t0_1);
+        z3_3 = add(z3_2, z3_2);
+
+        z3_4 = add(z3_3, z3_3);
+        t1_5 = mul(y, z);
+        t2_6 = square(z);
+
+        t2_7 = mul_b3(t2_6);
+        x3_8 = mul(t2_7, z3_4);
+        y3_9 = add(t0_1, t2_7);
+
+        z3_10 = mul(t1_5, z3_4);
+        t1_11 = add(t2_7, t2_7);
+        t2_12 = add(t1_11, t2_7);
+
+        t0_13 = sub(t0_1, t2_12);
+        y3_14 = mul(t0_13, y3_9);
+        y3_15 = add(x3_8, y3_14);
+
+        t1_16 = mul(x, y);
+        x3_17 = mul(t0_13, t1_16);
+        x3_18 = add(x3_17, x3_17);
+    }
+}
+
+straight_line! {
+    /// Both sides of the curve equation Y^2 Z = X^3 + b Z^3, multiplied by
+    /// 3 so that `3b` is the only constant needed.
+    pub fn curve_equation, const CURVE_EQUATION = (x, y, z) -> (lhs_4, rhs_12) {
+        t0_1 = square(y);
+        t1_2 = mul(t0_1, z);
+        t2_3 = add(t1_2, t1_2);
+        lhs_4 = add(t2_3, t1_2);
+
+        t3_5 = square(x);
+        t4_6 = mul(t3_5, x);
+        t5_7 = add(t4_6, t4_6);
+        t6_8 = add(t5_7, t4_6);
+
+        t7_9 = square(z);
+        t8_10 = mul(t7_9, z);
+        t9_11 = mul_b3(t8_10);
+        rhs_12 = add(t6_8, t9_11);
+    }
+}
+
+/// Every formula the crate ships.
+pub const PROGRAMS: [crate::ir::Program; 3] = [ADD, ADD_MIXED, DOUBLE];
@@ -1,29 +1,500 @@
+use halo2curves::bn256;
 use halo2curves::group::ff::Field;
+use halo2curves::group::ff::FromUniformBytes;
 use halo2curves::group::ff::PrimeField;
-use halo2curves::CurveExt;
+use halo2curves::group::{Group, GroupEncoding, ScalarMul, ScalarMulOwned, UncompressedEncoding};
+use halo2curves::pasta::{pallas, vesta};
+use halo2curves::{CurveAffine, CurveExt};
 
-pub fn add<C: CurveExt>(p1: &C, p2: &C) -> C {
-    let b3 = C::Base::from(3) * C::b();
-    let (x1, y1, z1) = p1.jacobian_coordinates();
-    let (x2, y2, z2) = p2.jacobian_coordinates();
-    let (x3, y3, z3) = core_add::<C>(x1, y1, z1, x2, y2, z2, b3);
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
-    // we need to path halo2curve in order to skip this step
+use rand_core::RngCore;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+use crate::formulas::{self, Rcb15Field};
+use crate::hash_to_curve::{hash_to_field, map_to_curve, SswuParams};
+use crate::opcount::{self, Formula, OpCount};
+pub use crate::opcount::{HOMOGENEOUS_TO_JACOBIAN, JACOBIAN_TO_HOMOGENEOUS};
+use crate::params::{FormulaClass, SubgroupCheck};
+
+macro_rules! impl_rcb15_field {
+    ($($field: ty),*) => {
+        $(
+            impl Rcb15Field for $field {
+                #[inline]
+                fn square(&self) -> Self {
+                    Field::square(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_rcb15_field!(bn256::Fq, pallas::Base, vesta::Base);
+
+macro_rules! impl_sswu_field {
+    ($($field: ty),*) => {
+        $(
+            impl crate::hash_to_curve::SswuField for $field {
+                fn zero() -> Self {
+                    Self::ZERO
+                }
+
+                fn one() -> Self {
+                    Self::ONE
+                }
+
+                fn ct_is_zero(&self) -> Choice {
+                    Field::is_zero(self)
+                }
+
+                fn ct_select(a: &Self, b: &Self, choice: Choice) -> Self {
+                    Self::conditional_select(a, b, choice)
+                }
+
+                fn sgn0(&self) -> Choice {
+                    self.is_odd()
+                }
+
+                fn from_okm(okm: &[u8]) -> Self {
+                    // `from_uniform_bytes` reads a little-endian integer
+                    let mut bytes = [0; 64];
+                    bytes.copy_from_slice(okm);
+                    bytes.reverse();
+                    Self::from_uniform_bytes(&bytes)
+                }
+            }
+        )*
+    };
+}
+
+impl_sswu_field!(pallas::Base, vesta::Base);
+
+/// Per-curve constants used by the RCB15 formulas.
+///
+/// These are fixed by the curve, so they are computed once here instead of on
+/// every call to `add` or `double`.
+pub trait Rcb15Params: CurveExt<Base: Rcb15Field> {
+    /// `3 * b`, the only curve constant used by the a = 0 formulas.
+    const B3: Self::Base;
+    /// Multiply by `B3`. Curves with a small `b` override this with an
+    /// addition chain; the default is a full field multiplication.
+    #[inline]
+    fn mul_by_b3(x: &Self::Base) -> Self::Base {
+        Self::B3 * *x
+    }
+    /// Which family of RCB15 formulas applies to this curve. Only `AZero` is
+    /// implemented, which the formulas check in debug builds.
+    const FORMULA: FormulaClass;
+    /// A primitive cube root of unity such that `(x, y) -> (beta * x, y)`
+    /// acts as multiplication by `ENDO_LAMBDA` on the prime-order subgroup.
+    const ENDO_BETA: Self::Base;
+    /// The eigenvalue of the GLV endomorphism.
+    const ENDO_LAMBDA: Self::ScalarExt;
+    /// How membership in the prime-order subgroup should be checked.
+    const SUBGROUP_CHECK: SubgroupCheck;
+}
+
+impl Rcb15Params for bn256::G1 {
+    const B3: bn256::Fq = bn256::Fq::from_raw([9, 0, 0, 0]);
+
+    #[inline]
+    fn mul_by_b3(x: &bn256::Fq) -> bn256::Fq {
+        // 9x = 8x + x
+        x.double().double().double() + x
+    }
+
+    const FORMULA: FormulaClass = FormulaClass::AZero;
+    const ENDO_BETA: bn256::Fq = bn256::Fq::from_raw([
+        0xe4bd44e5607cfd48,
+        0xc28f069fbb966e3d,
+        0x5e6dd9e7e0acccb0,
+        0x30644e72e131a029,
+    ]);
+    const ENDO_LAMBDA: bn256::Fr = bn256::Fr::from_raw([
+        0xb8ca0b2d36636f23,
+        0xcc37a73fec2bc5e9,
+        0x048b6e193fd84104,
+        0x30644e72e131a029,
+    ]);
+    const SUBGROUP_CHECK: SubgroupCheck = SubgroupCheck::CofactorOne;
+}
+
+impl Rcb15Params for pallas::Point {
+    const B3: pallas::Base = pallas::Base::from_raw([15, 0, 0, 0]);
+
+    #[inline]
+    fn mul_by_b3(x: &pallas::Base) -> pallas::Base {
+        // 15x = 16x - x
+        x.double().double().double().double() - x
+    }
+
+    const FORMULA: FormulaClass = FormulaClass::AZero;
+    const ENDO_BETA: pallas::Base = pallas::Base::from_raw([
+        0x7b7fd22f0201b547,
+        0x05270d29d19fc7d2,
+        0xd3552a23a8554e50,
+        0x2d33357cb532458e,
+    ]);
+    const ENDO_LAMBDA: pallas::Scalar = pallas::Scalar::from_raw([
+        0x619d1840af55f1b1,
+        0x1259527ec1d4752e,
+        0xaee24b27e308f0a6,
+        0x397e65a7d7c1ad71,
+    ]);
+    const SUBGROUP_CHECK: SubgroupCheck = SubgroupCheck::CofactorOne;
+}
+
+impl Rcb15Params for vesta::Point {
+    const B3: vesta::Base = vesta::Base::from_raw([15, 0, 0, 0]);
+
+    #[inline]
+    fn mul_by_b3(x: &vesta::Base) -> vesta::Base {
+        // 15x = 16x - x
+        x.double().double().double().double() - x
+    }
+
+    const FORMULA: FormulaClass = FormulaClass::AZero;
+    const ENDO_BETA: vesta::Base = vesta::Base::from_raw([
+        0x2aa9d2e050aa0e4f,
+        0x0fed467d47c033af,
+        0x511db4d81cf70f5a,
+        0x06819a58283e528e,
+    ]);
+    const ENDO_LAMBDA: vesta::Scalar = vesta::Scalar::from_raw([
+        0x1dad5ebdfdfe4ab9,
+        0x1d1f8bd237ad3149,
+        0x2caad5dc57aab1b0,
+        0x12ccca834acdba71,
+    ]);
+    const SUBGROUP_CHECK: SubgroupCheck = SubgroupCheck::CofactorOne;
+}
+
+/// The suite `pallas_XMD:BLAKE2b_SSWU_RO_` of Zcash and `pasta_curves`:
+/// Z = -13 and the 3-isogeny from iso-Pallas, y^2 = x^3 + A'x + 1265.
+impl SswuParams for pallas::Point {
+    type Field = pallas::Base;
+    type Hash = blake2::Blake2b512;
+    const L: usize = 64;
+    const Z: pallas::Base = pallas::Base::from_raw([
+        0x992d30ecfffffff4,
+        0x224698fc094cf91b,
+        0x0000000000000000,
+        0x4000000000000000,
+    ]);
+    const ISO_A: pallas::Base = pallas::Base::from_raw([
+        0x92bb4b0b657a014b,
+        0xb74134581a27a59f,
+        0x49be2d7258370742,
+        0x18354a2eb0ea8c9c,
+    ]);
+    const ISO_B: pallas::Base = pallas::Base::from_raw([1265, 0, 0, 0]);
+    const ISO_X_NUM: &'static [pallas::Base] = &[
+        pallas::Base::from_raw([
+            0xeebec06955555580,
+            0x8102eea8e7b06eb6,
+            0xc71c71c71c71c71c,
+            0x1c71c71c71c71c71,
+        ]),
+        pallas::Base::from_raw([
+            0x0eb64faef37ea4f7,
+            0x380af066cfeb6d69,
+            0x98c7d7ac3d98fd13,
+            0x17329b9ec5253753,
+        ]),
+        pallas::Base::from_raw([
+            0x8cf863b02814fb76,
+            0x0f93b82ee4b99495,
+            0x267c7ffa51cf412a,
+            0x3509afd51872d88e,
+        ]),
+        pallas::Base::from_raw([
+            0x775f6034aaaaaaab,
+            0x4081775473d8375b,
+            0xe38e38e38e38e38e,
+            0x0e38e38e38e38e38,
+        ]),
+    ];
+    const ISO_X_DEN: &'static [pallas::Base] = &[
+        pallas::Base::from_raw([
+            0x2aa3af1eae5b6604,
+            0xb4abf9fb9a1fc81c,
+            0x1d13bf2a7f22b105,
+            0x325669becaecd5d1,
+        ]),
+        pallas::Base::from_raw([
+            0xc47f2ab668bcd71f,
+            0x9c434ac1c96b6980,
+            0x5a607fcce0494a79,
+            0x1d572e7ddc099cff,
+        ]),
+        pallas::Base::ONE,
+    ];
+    const ISO_Y_NUM: &'static [pallas::Base] = &[
+        pallas::Base::from_raw([
+            0x93e53ab371c71c4f,
+            0x0ac03e8e134eb3e4,
+            0x7b425ed097b425ed,
+            0x025ed097b425ed09,
+        ]),
+        pallas::Base::from_raw([
+            0x02e2be87d225b234,
+            0x1765e924f7459378,
+            0x303216cce1db9ff1,
+            0x3fb98ff0d2ddcadd,
+        ]),
+        pallas::Base::from_raw([
+            0xc67c31d8140a7dbb,
+            0x07c9dc17725cca4a,
+            0x133e3ffd28e7a095,
+            0x1a84d7ea8c396c47,
+        ]),
+        pallas::Base::from_raw([
+            0x5ad985b5e38e38e4,
+            0x7642b01ad461bad2,
+            0x4bda12f684bda12f,
+            0x1a12f684bda12f68,
+        ]),
+    ];
+    const ISO_Y_DEN: &'static [pallas::Base] = &[
+        pallas::Base::from_raw([
+            0x992d30ecfffffde5,
+            0x224698fc094cf91b,
+            0x0000000000000000,
+            0x4000000000000000,
+        ]),
+        pallas::Base::from_raw([
+            0x4d90ab820b12320a,
+            0xd976bbfabbc5661d,
+            0x573b3d7f7d681310,
+            0x17033d3c60c68173,
+        ]),
+        pallas::Base::from_raw([
+            0x5a28279b1d1b42ae,
+            0x5941a3a4a97aa1b3,
+            0x0790bfb3506defb6,
+            0x0c02c5bcca0e6b7f,
+        ]),
+        pallas::Base::ONE,
+    ];
+
+    fn sqrt_ratio(u: &pallas::Base, v: &pallas::Base) -> (Choice, pallas::Base) {
+        // a square root of Z / ROOT_OF_UNITY
+        let theta = pallas::Base::from_raw([
+            0xca330bcc09ac318e,
+            0x51f64fc4dc888857,
+            0x4647aef782d5cdc8,
+            0x0f7bdb65814179b4,
+        ]);
+        sqrt_ratio(u, v, theta)
+    }
+}
+
+/// The suite `vesta_XMD:BLAKE2b_SSWU_RO_` of Zcash and `pasta_curves`:
+/// Z = -13 and the 3-isogeny from iso-Vesta, y^2 = x^3 + A'x + 1265.
+impl SswuParams for vesta::Point {
+    type Field = vesta::Base;
+    type Hash = blake2::Blake2b512;
+    const L: usize = 64;
+    const Z: vesta::Base = vesta::Base::from_raw([
+        0x8c46eb20fffffff4,
+        0x224698fc0994a8dd,
+        0x0000000000000000,
+        0x4000000000000000,
+    ]);
+    const ISO_A: vesta::Base = vesta::Base::from_raw([
+        0xc515ad7242eaa6b1,
+        0x9673928c7d01b212,
+        0x81639c4d96f78773,
+        0x267f9b2ee592271a,
+    ]);
+    const ISO_B: vesta::Base = vesta::Base::from_raw([1265, 0, 0, 0]);
+    const ISO_X_NUM: &'static [vesta::Base] = &[
+        vesta::Base::from_raw([
+            0xfb539a6f0000002b,
+            0xe1c521a795ac8356,
+            0x1c71c71c71c71c71,
+            0x31c71c71c71c71c7,
+        ]),
+        vesta::Base::from_raw([
+            0xaeac67bbeb586a3d,
+            0xd59d03d23b39cb11,
+            0xed7ee4a9cdf78f8f,
+            0x18760c7f7a9ad20d,
+        ]),
+        vesta::Base::from_raw([
+            0x8b95c6aaf703bcc5,
+            0x216b8861ec72bd5d,
+            0xacecf10f5f7c09a2,
+            0x1d935247b4473d17,
+        ]),
+        vesta::Base::from_raw([
+            0x43cd42c800000001,
+            0x0205dd51cfa0961a,
+            0x8e38e38e38e38e39,
+            0x38e38e38e38e38e3,
+        ]),
+    ];
+    const ISO_X_DEN: &'static [vesta::Base] = &[
+        vesta::Base::from_raw([
+            0xf169c187d2533465,
+            0x30cd6d53df49d235,
+            0x0c621de8b91c242a,
+            0x14735171ee542778,
+        ]),
+        vesta::Base::from_raw([
+            0xb7284f7eaf21a2e9,
+            0xa3ad678129b604d3,
+            0x1454798a5b5c56b2,
+            0x0a2de485568125d5,
+        ]),
+        vesta::Base::ONE,
+    ];
+    const ISO_Y_NUM: &'static [vesta::Base] = &[
+        vesta::Base::from_raw([
+            0x8f64842c55555533,
+            0x8bc32d36fb21a6a3,
+            0x425ed097b425ed09,
+            0x1ed097b425ed097b,
+        ]),
+        vesta::Base::from_raw([
+            0x4986913ab4443034,
+            0x97a3ca5c24e9ea63,
+            0x66d1466e9de10e64,
+            0x19b0d87e16e25788,
+        ]),
+        vesta::Base::from_raw([
+            0x8bee58e5fb81de63,
+            0x21d910aefb03b31d,
+            0xd6767887afbe04d1,
+            0x2ec9a923da239e8b,
+        ]),
+        vesta::Base::from_raw([
+            0x6bef1642aaaaaaab,
+            0x5601f4709a8adcb3,
+            0xda12f684bda12f68,
+            0x12f684bda12f684b,
+        ]),
+    ];
+    const ISO_Y_DEN: &'static [vesta::Base] = &[
+        vesta::Base::from_raw([
+            0x8c46eb20fffffde5,
+            0x224698fc0994a8dd,
+            0x0000000000000000,
+            0x4000000000000000,
+        ]),
+        vesta::Base::from_raw([
+            0xd43d449776f99d2f,
+            0x926847fb9ddd76a1,
+            0x252659ba2b546c7e,
+            0x3d59f455cafc7668,
+        ]),
+        vesta::Base::from_raw([
+            0x58dfecce86b2745e,
+            0x06a767bfc35b5bac,
+            0x9e7eb64f890a820c,
+            0x2f44d6c801c1b8bf,
+        ]),
+        vesta::Base::ONE,
+    ];
+
+    fn sqrt_ratio(u: &vesta::Base, v: &vesta::Base) -> (Choice, vesta::Base) {
+        // a square root of Z / ROOT_OF_UNITY
+        let theta = vesta::Base::from_raw([
+            0x632cae9872df1b5d,
+            0x38578ccadf03ac27,
+            0x53c3808d9e2f2357,
+            0x2b3483a1ee9a382f,
+        ]);
+        sqrt_ratio(u, v, theta)
+    }
+}
+
+/// `sqrt_ratio` of RFC 9380 from the one of `ff`, which returns a square
+/// root of `ROOT_OF_UNITY * u / v` when `u / v` is not a square. `theta` is a
+/// square root of `Z / ROOT_OF_UNITY`, so multiplying by it gives the square
+/// root of `Z * u / v` that RFC 9380 asks for.
+fn sqrt_ratio<F: Field>(u: &F, v: &F, theta: F) -> (Choice, F) {
+    let (is_square, y) = F::sqrt_ratio(u, v);
+    (
+        is_square,
+        F::conditional_select(&(theta * y), &y, is_square),
+    )
+}
+
+/// The formulas map points of the curve to points of the curve, and the
+/// curves have odd order, so the result is never (0 : 0 : 0). Inputs off the
+/// curve, e.g. from a fuzzer, are not checked.
+fn debug_check<C: Rcb15Params>(
+    inputs: &[(C::Base, C::Base, C::Base)],
+    (x, y, z): (C::Base, C::Base, C::Base),
+) {
+    debug_assert_eq!(C::FORMULA, FormulaClass::AZero);
+    let on_curve =
+        |x, y, z| bool::from(is_on_curve_homogeneous(&HomogeneousPoint::<C> { x, y, z }));
+    debug_assert!(
+        !inputs.iter().all(|&(x, y, z)| on_curve(x, y, z)) || on_curve(x, y, z),
+        "the result of an RCB15 formula is not on the curve"
+    );
+}
+
+pub fn add<C: Rcb15Params>(p1: &C, p2: &C) -> C {
+    let (x1, y1, z1) = jacobian_to_homogeneous(p1);
+    let (x2, y2, z2) = jacobian_to_homogeneous(p2);
+    let (x3, y3, z3) = formulas::add(x1, y1, z1, x2, y2, z2, C::mul_by_b3);
+    debug_check::<C>(&[(x1, y1, z1), (x2, y2, z2)], (x3, y3, z3));
+
+    // halo2curves only stores Jacobian coordinates, so convert back
     let (x3, y3, z3) = homogeneous_to_jacobian::<C>(x3, y3, z3);
     C::new_jacobian(x3, y3, z3).unwrap()
 }
 
-pub fn double<C: CurveExt>(p: &C) -> C {
-    let b3 = C::Base::from(3) * C::b();
-    let (x1, y1, z1) = p.jacobian_coordinates();
-    let (x3, y3, z3) = core_double::<C>(x1, y1, z1, b3);
+pub fn double<C: Rcb15Params>(p: &C) -> C {
+    let (x1, y1, z1) = jacobian_to_homogeneous(p);
+    let (x3, y3, z3) = formulas::double(x1, y1, z1, C::mul_by_b3);
+    debug_check::<C>(&[(x1, y1, z1)], (x3, y3, z3));
+
+    // halo2curves only stores Jacobian coordinates, so convert back
+    let (x3, y3, z3) = homogeneous_to_jacobian::<C>(x3, y3, z3);
+    C::new_jacobian(x3, y3, z3).unwrap()
+}
+
+/// Mixed addition of a point and an affine point, i.e. the second operand
+/// has Z = 1. The affine identity has no coordinates, so that case is
+/// handled separately.
+pub fn add_mixed<C>(p1: &C, p2: &C::AffineExt) -> C
+where
+    C: Rcb15Params,
+    C::AffineExt: CurveAffine<Base = C::Base>,
+{
+    let coordinates = p2.coordinates();
+    if coordinates.is_none().into() {
+        return *p1;
+    }
+    let coordinates = coordinates.unwrap();
+    let (x1, y1, z1) = jacobian_to_homogeneous(p1);
+    let (x3, y3, z3) =
+        formulas::add_mixed(x1, y1, z1, *coordinates.x(), *coordinates.y(), C::mul_by_b3);
+    debug_check::<C>(
+        &[
+            (x1, y1, z1),
+            (*coordinates.x(), *coordinates.y(), C::Base::ONE),
+        ],
+        (x3, y3, z3),
+    );
 
-    // we need to path halo2curve in order to skip this step
+    // halo2curves only stores Jacobian coordinates, so convert back
     let (x3, y3, z3) = homogeneous_to_jacobian::<C>(x3, y3, z3);
     C::new_jacobian(x3, y3, z3).unwrap()
 }
 
-pub fn mul<C: CurveExt>(base: &C, scalar: &C::ScalarExt) -> C {
+/// Field-operation counts of `formula` on this curve.
+pub fn op_count<C: Rcb15Params>(formula: Formula) -> OpCount {
+    opcount::count(formula, C::Base::ONE, C::mul_by_b3)
+}
+
+pub fn mul<C: Rcb15Params>(base: &C, scalar: &C::ScalarExt) -> C {
     let mut res = None;
     for b in scalar
         .to_repr()
@@ -43,138 +514,519 @@ pub fn mul<C: CurveExt>(base: &C, scalar: &C::ScalarExt) -> C {
             }
         }
     }
-    res.unwrap()
+    // a zero scalar has no set bits
+    res.unwrap_or_else(C::identity)
 }
 
+/// Jacobian (X : Y : Z) is the affine point (X/Z^2, Y/Z^3), which is
+/// (XZ : Y : Z^3) in homogeneous coordinates. The identity, stored by
+/// halo2curves with Z = 0 and arbitrary X and Y, maps to (0 : 1 : 0).
+#[inline]
+pub fn jacobian_to_homogeneous<C: CurveExt>(p: &C) -> (C::Base, C::Base, C::Base) {
+    let (x, y, z) = p.jacobian_coordinates();
+    let y = C::Base::conditional_select(&y, &C::Base::ONE, z.is_zero());
+    (x * z, y, z.square() * z)
+}
+
+/// Homogeneous (X : Y : Z) is the affine point (X/Z, Y/Z), which is
+/// (XZ : YZ^2 : Z) in Jacobian coordinates. No inversion is needed, and
+/// (0 : 1 : 0) maps to a Jacobian point with Z = 0.
 #[inline]
 pub fn homogeneous_to_jacobian<C: CurveExt>(
     x: C::Base,
     y: C::Base,
     z: C::Base,
 ) -> (C::Base, C::Base, C::Base) {
-    let z = z.invert().unwrap();
-    (x * z, y * z, C::Base::from_u128(1))
+    (x * z, y * z.square(), z)
 }
 
+/// Points returned by this module are ordinary halo2curves points, so this
+/// is the usual normalisation.
 pub fn homogeneous_form_to_affine<C: CurveExt>(p: &C) -> C::Affine {
-    let (x, y, z) = p.jacobian_coordinates();
-    let z = z.invert().unwrap();
-    C::new_jacobian(x * z, y * z, C::Base::from_u128(1))
-        .unwrap()
-        .to_affine()
+    p.to_affine()
 }
 
-pub fn naive_msm<C: CurveExt>(points: &[C], scalars: &[C::ScalarExt]) -> C {
-    let mut res = mul(&points[0], &scalars[0]);
-    for (p, s) in points.iter().zip(scalars.iter()).skip(1) {
-        let tmp = mul(p, s);
-        res = add(&res, &tmp)
+/// A point in homogeneous coordinates.
+///
+/// halo2curves points store Jacobian coordinates, so this keeps the
+/// (X : Y : Z) of the formulas, e.g. for an accumulator that is written out
+/// between steps of a long computation.
+#[derive(Clone, Copy, Debug)]
+pub struct HomogeneousPoint<C: CurveExt> {
+    pub x: C::Base,
+    pub y: C::Base,
+    pub z: C::Base,
+}
+
+impl<C: CurveExt> From<C> for HomogeneousPoint<C> {
+    fn from(p: C) -> Self {
+        let (x, y, z) = jacobian_to_homogeneous(&p);
+        Self { x, y, z }
+    }
+}
+
+impl<C: CurveExt> ConditionallySelectable for HomogeneousPoint<C> {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self {
+            x: C::Base::conditional_select(&a.x, &b.x, choice),
+            y: C::Base::conditional_select(&a.y, &b.y, choice),
+            z: C::Base::conditional_select(&a.z, &b.z, choice),
+        }
+    }
+}
+
+/// Equality of the points, not of the triples: (X1 : Y1 : Z1) = (X2 : Y2 : Z2)
+/// iff X1 Z2 = X2 Z1 and Y1 Z2 = Y2 Z1, so no inversion is needed. Both
+/// sides are the identity iff both Z are zero. Only meaningful for points on
+/// the curve.
+impl<C: CurveExt> ConstantTimeEq for HomogeneousPoint<C> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        let z1_is_zero = self.z.is_zero();
+        let z2_is_zero = other.z.is_zero();
+        let same = (self.x * other.z).ct_eq(&(other.x * self.z))
+            & (self.y * other.z).ct_eq(&(other.y * self.z));
+        (z1_is_zero & z2_is_zero) | (!z1_is_zero & !z2_is_zero & same)
+    }
+}
+
+impl<C: CurveExt> PartialEq for HomogeneousPoint<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<C: CurveExt> Eq for HomogeneousPoint<C> {}
+
+impl<C: Rcb15Params> HomogeneousPoint<C> {
+    pub fn identity() -> Self {
+        Self {
+            x: C::Base::ZERO,
+            y: C::Base::ONE,
+            z: C::Base::ZERO,
+        }
+    }
+
+    /// Panics if the point is not on the curve, which can only happen after
+    /// an unchecked decoding.
+    pub fn to_curve(&self) -> C {
+        let (x, y, z) = homogeneous_to_jacobian::<C>(self.x, self.y, self.z);
+        C::new_jacobian(x, y, z).unwrap()
+    }
+
+    /// A uniform point of the prime-order subgroup, like `Group::random`,
+    /// but scaled by a random non-zero factor so that Z is not one.
+    pub fn random_with_random_z(mut rng: impl RngCore) -> Self {
+        let mut p = Self::from(C::random(&mut rng));
+        p.randomize_representation(rng);
+        p
+    }
+
+    /// Replaces (X : Y : Z) by (λX : λY : λZ) for a random non-zero λ. The
+    /// point is unchanged, but its representation is not predictable, which
+    /// blinds the intermediate values of a computation against side channels.
+    pub fn randomize_representation(&mut self, mut rng: impl RngCore) {
+        let lambda = loop {
+            let lambda = C::Base::random(&mut rng);
+            if !bool::from(lambda.is_zero()) {
+                break lambda;
+            }
+        };
+        self.x *= lambda;
+        self.y *= lambda;
+        self.z *= lambda;
+    }
+}
+
+fn is_zero_triple<C: CurveExt>(p: &HomogeneousPoint<C>) -> Choice {
+    p.x.is_zero() & p.y.is_zero() & p.z.is_zero()
+}
+
+/// Whether (X : Y : Z) is a point of the curve, i.e. Y^2 Z = X^3 + b Z^3 and
+/// not (0 : 0 : 0), checked in constant time and without an inversion. The
+/// identity (0 : 1 : 0) is on the curve.
+pub fn is_on_curve_homogeneous<C: Rcb15Params>(p: &HomogeneousPoint<C>) -> Choice {
+    let (lhs, rhs) = formulas::curve_equation(p.x, p.y, p.z, C::mul_by_b3);
+    lhs.ct_eq(&rhs) & !is_zero_triple(p)
+}
+
+/// [`is_on_curve_homogeneous`] for any short Weierstrass curve, i.e.
+/// Y^2 Z = X^3 + a X Z^2 + b Z^3.
+pub fn is_on_curve_homogeneous_generic<C: CurveExt>(p: &HomogeneousPoint<C>) -> Choice {
+    let z2 = p.z.square();
+    let lhs = p.y.square() * p.z;
+    let rhs = p.x.square() * p.x + C::a() * p.x * z2 + C::b() * z2 * p.z;
+    lhs.ct_eq(&rhs) & !is_zero_triple(p)
+}
+
+/// Whether (X : Y : Z) is on the curve and in the prime-order subgroup. The
+/// supported curves have prime order, so with `CofactorOne` this is
+/// [`is_on_curve_homogeneous`].
+pub fn is_in_subgroup<C: Rcb15Params>(p: &HomogeneousPoint<C>) -> Choice {
+    let on_curve = is_on_curve_homogeneous(p);
+    match C::SUBGROUP_CHECK {
+        SubgroupCheck::CofactorOne => on_curve,
+        _ => {
+            // `to_curve` panics off the curve
+            let p =
+                HomogeneousPoint::conditional_select(&HomogeneousPoint::identity(), p, on_curve);
+            on_curve & is_in_subgroup_naive(&p.to_curve())
+        }
+    }
+}
+
+/// `[r]P = O` with the RCB15 `mul`. `r` is zero as a scalar, so this checks
+/// `[r - 1]P = -P` instead.
+pub fn is_in_subgroup_naive<C: Rcb15Params>(p: &C) -> Choice {
+    mul(p, &-C::ScalarExt::ONE).ct_eq(&-*p)
+}
+
+/// The compressed encoding of the normalised point, as halo2curves encodes
+/// `C`.
+impl<C: Rcb15Params> GroupEncoding for HomogeneousPoint<C> {
+    type Repr = <C as GroupEncoding>::Repr;
+
+    fn from_bytes(bytes: &Self::Repr) -> CtOption<Self> {
+        let p = C::from_bytes(bytes);
+        CtOption::new(Self::from(p.unwrap_or(C::identity())), p.is_some())
+    }
+
+    fn from_bytes_unchecked(bytes: &Self::Repr) -> CtOption<Self> {
+        let p = C::from_bytes_unchecked(bytes);
+        CtOption::new(Self::from(p.unwrap_or(C::identity())), p.is_some())
+    }
+
+    fn to_bytes(&self) -> Self::Repr {
+        self.to_curve().to_bytes()
+    }
+}
+
+/// The uncompressed encoding of a [`HomogeneousPoint`]: X, Y and Z as they
+/// are, each in the canonical encoding of the base field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HomogeneousBytes(pub [u8; 96]);
+
+impl Default for HomogeneousBytes {
+    fn default() -> Self {
+        HomogeneousBytes([0; 96])
+    }
+}
+
+impl AsRef<[u8]> for HomogeneousBytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsMut<[u8]> for HomogeneousBytes {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+/// Decoding checks that the point is on the curve and in the prime-order
+/// subgroup.
+impl<C: Rcb15Params> UncompressedEncoding for HomogeneousPoint<C>
+where
+    C::Base: PrimeField<Repr = [u8; 32]>,
+{
+    type Uncompressed = HomogeneousBytes;
+
+    fn from_uncompressed(bytes: &Self::Uncompressed) -> CtOption<Self> {
+        let p = Self::from_uncompressed_unchecked(bytes);
+        let valid = p.is_some() & is_in_subgroup(&p.unwrap_or(Self::identity()));
+        CtOption::new(p.unwrap_or(Self::identity()), valid)
+    }
+
+    fn from_uncompressed_unchecked(bytes: &Self::Uncompressed) -> CtOption<Self> {
+        let coordinate = |i: usize| {
+            let mut repr = [0; 32];
+            repr.copy_from_slice(&bytes.0[32 * i..32 * (i + 1)]);
+            C::Base::from_repr(repr)
+        };
+        let (x, y, z) = (coordinate(0), coordinate(1), coordinate(2));
+        let p = Self {
+            x: x.unwrap_or(C::Base::ZERO),
+            y: y.unwrap_or(C::Base::ZERO),
+            z: z.unwrap_or(C::Base::ZERO),
+        };
+        CtOption::new(p, x.is_some() & y.is_some() & z.is_some())
+    }
+
+    fn to_uncompressed(&self) -> Self::Uncompressed {
+        let mut bytes = HomogeneousBytes::default();
+        for (i, coordinate) in [self.x, self.y, self.z].iter().enumerate() {
+            bytes.0[32 * i..32 * (i + 1)].copy_from_slice(&coordinate.to_repr());
+        }
+        bytes
+    }
+}
+
+/// `hash_to_curve` as Zcash and `pasta_curves` define it, with the suite
+/// `{domain_prefix}-{CURVE_ID}_XMD:BLAKE2b_SSWU_RO_`. The two outputs of
+/// `map_to_curve` are added with the RCB15 formulas. The supported curves
+/// have prime order, so there is no cofactor to clear.
+pub fn hash_to_curve<C>(domain_prefix: &str, msg: &[u8]) -> HomogeneousPoint<C>
+where
+    C: Rcb15Params + SswuParams<Field = <C as CurveExt>::Base>,
+{
+    debug_assert_eq!(C::SUBGROUP_CHECK, SubgroupCheck::CofactorOne);
+    let dst = format!("{}-{}_XMD:BLAKE2b_SSWU_RO_", domain_prefix, C::CURVE_ID);
+    let [u0, u1] = hash_to_field::<C>(msg, dst.as_bytes());
+    let (x0, y0, z0) = map_to_curve::<C>(&u0);
+    let (x1, y1, z1) = map_to_curve::<C>(&u1);
+    let (x, y, z) = formulas::add(x0, y0, z0, x1, y1, z1, C::mul_by_b3);
+    debug_check::<C>(&[(x0, y0, z0), (x1, y1, z1)], (x, y, z));
+    HomogeneousPoint { x, y, z }
+}
+
+fn double_homogeneous<C: Rcb15Params>(p: &HomogeneousPoint<C>) -> HomogeneousPoint<C> {
+    let (x, y, z) = formulas::double(p.x, p.y, p.z, C::mul_by_b3);
+    debug_check::<C>(&[(p.x, p.y, p.z)], (x, y, z));
+    HomogeneousPoint { x, y, z }
+}
+
+/// Scalar multiplication on homogeneous points, in constant time: every bit
+/// of the scalar's representation costs a doubling, an addition and a
+/// selection. The formulas are complete, so the identity needs no branch.
+pub fn mul_homogeneous<C: Rcb15Params>(
+    base: &HomogeneousPoint<C>,
+    scalar: &C::ScalarExt,
+) -> HomogeneousPoint<C> {
+    mul_homogeneous_from(
+        HomogeneousPoint::identity(),
+        base,
+        scalar.to_repr().as_ref(),
+    )
+}
+
+/// [`mul_homogeneous`] with randomised representations: the accumulator
+/// starts at (0 : λ : 0) and the base is scaled by an independent random
+/// factor, so no intermediate triple is predictable from the inputs. The
+/// result is the same point as `mul_homogeneous`.
+pub fn mul_homogeneous_randomized<C: Rcb15Params>(
+    base: &HomogeneousPoint<C>,
+    scalar: &C::ScalarExt,
+    mut rng: impl RngCore,
+) -> HomogeneousPoint<C> {
+    let mut start = HomogeneousPoint::identity();
+    start.randomize_representation(&mut rng);
+    let mut base = *base;
+    base.randomize_representation(&mut rng);
+    mul_homogeneous_from(start, &base, scalar.to_repr().as_ref())
+}
+
+/// Scalar multiplication with a blinded scalar: the point is (k + m r) P for
+/// a random 64-bit m, where r is the order of the scalar field, which is kP
+/// for every P of the curve. The ladder is that of [`mul_homogeneous`], 72
+/// bits longer; the formulas are complete, so the extra bits cannot reach an
+/// exceptional case.
+pub fn mul_blinded<C: Rcb15Params>(base: &C, scalar: &C::ScalarExt, mut rng: impl RngCore) -> C {
+    let k = blinded_scalar(scalar, rng.next_u64());
+    mul_homogeneous_from(
+        HomogeneousPoint::identity(),
+        &HomogeneousPoint::from(*base),
+        &k,
+    )
+    .to_curve()
+}
+
+/// The little-endian bytes of k + m r, computed as k + m (r - 1) + m since
+/// the representation of -1 is r - 1. The length only depends on the field.
+fn blinded_scalar<F: PrimeField>(k: &F, m: u64) -> Vec<u8> {
+    let k = k.to_repr();
+    let r_minus_one = (-F::ONE).to_repr();
+    let (k, r_minus_one) = (k.as_ref(), r_minus_one.as_ref());
+
+    let mut res = Vec::with_capacity(k.len() + 9);
+    let mut carry = m as u128;
+    for (k_i, r_i) in k.iter().zip(r_minus_one) {
+        let acc = *k_i as u128 + m as u128 * *r_i as u128 + carry;
+        res.push(acc as u8);
+        carry = acc >> 8;
+    }
+    // k + m r < 2^(8 len) (m + 1), so nine more bytes hold the carry
+    for _ in 0..9 {
+        res.push(carry as u8);
+        carry >>= 8;
     }
     res
 }
 
-fn core_add<C: CurveExt>(
-    x1: C::Base,
-    y1: C::Base,
-    z1: C::Base,
-    x2: C::Base,
-    y2: C::Base,
-    z2: C::Base,
-    b3: C::Base,
-) -> (C::Base, C::Base, C::Base) {
-    // Algorithm 7 of eprint:2015-1060
-    // Source code from A.3
-    let t0_1 = x1 * x2; // mul #1
-    let t1_2 = y1 * y2; // mul #2
-    let t2_3 = z1 * z2; // mul #3
+/// The double-and-add ladder of [`mul_homogeneous`] from `res`, over the
+/// little-endian bytes `scalar`.
+fn mul_homogeneous_from<C: Rcb15Params>(
+    mut res: HomogeneousPoint<C>,
+    base: &HomogeneousPoint<C>,
+    scalar: &[u8],
+) -> HomogeneousPoint<C> {
+    for byte in scalar.iter().rev() {
+        for i in (0..8).rev() {
+            res = double_homogeneous(&res);
+            let sum = res + base;
+            res = HomogeneousPoint::conditional_select(&res, &sum, Choice::from((byte >> i) & 1));
+        }
+    }
+    res
+}
+
+// `Mul<C::ScalarExt>` and `Mul<&C::ScalarExt>` overlap for a generic `C`, as
+// coherence cannot tell the projections apart, so scalar multiplication is
+// implemented per curve.
+macro_rules! impl_scalar_mul {
+    ($($curve: ty, $scalar: ty);*) => {
+        $(
+            impl Mul<&$scalar> for HomogeneousPoint<$curve> {
+                type Output = Self;
+
+                fn mul(self, rhs: &$scalar) -> Self {
+                    mul_homogeneous(&self, rhs)
+                }
+            }
+
+            impl Mul<$scalar> for HomogeneousPoint<$curve> {
+                type Output = Self;
+
+                fn mul(self, rhs: $scalar) -> Self {
+                    mul_homogeneous(&self, &rhs)
+                }
+            }
+
+            impl MulAssign<&$scalar> for HomogeneousPoint<$curve> {
+                fn mul_assign(&mut self, rhs: &$scalar) {
+                    *self = mul_homogeneous(self, rhs);
+                }
+            }
 
-    let t3_4 = x1 + y1;
-    let t4_5 = x2 + y2;
-    let t3_6 = t3_4 * t4_5; // mul #4
+            impl MulAssign<$scalar> for HomogeneousPoint<$curve> {
+                fn mul_assign(&mut self, rhs: $scalar) {
+                    *self = mul_homogeneous(self, &rhs);
+                }
+            }
+        )*
+    };
+}
 
-    let t4_7 = t0_1 + t1_2;
-    let t3_8 = t3_6 - t4_7;
-    let t4_9 = y1 + z1;
+impl_scalar_mul!(
+    bn256::G1, bn256::Fr;
+    pallas::Point, pallas::Scalar;
+    vesta::Point, vesta::Scalar
+);
 
-    let x3_10 = y2 + z2;
-    let t4_11 = t4_9 * x3_10; // mul #5
-    let x3_12 = t1_2 + t2_3;
+/// The group law of the curve on homogeneous points, with the RCB15
+/// formulas. `random` returns a normalised point (Z = 1).
+impl<C: Rcb15Params> Group for HomogeneousPoint<C>
+where
+    Self: ScalarMul<C::ScalarExt> + ScalarMulOwned<C::ScalarExt>,
+{
+    type Scalar = C::ScalarExt;
 
-    let t4_13 = t4_11 - x3_12;
-    let x3_14 = x1 + z1;
-    let y3_15 = x2 + z2;
+    fn random(rng: impl RngCore) -> Self {
+        Self::from(C::random(rng))
+    }
 
-    let x3_16 = x3_14 * y3_15; // mul #6
-    let y3_17 = t0_1 + t2_3;
-    let y3_18 = x3_16 - y3_17;
+    fn identity() -> Self {
+        HomogeneousPoint::identity()
+    }
 
-    let x3_19 = t0_1 + t0_1;
-    let t0_20 = x3_19 + t0_1;
-    // b3 is a constant -- so this multiplication is cheap
-    let t2_21 = b3 * t2_3;
+    fn generator() -> Self {
+        Self::from(C::generator())
+    }
 
-    let z3_22 = t1_2 + t2_21;
-    let t1_23 = t1_2 - t2_21;
-    // b3 is a constant -- so this multiplication is cheap
-    let y3_24 = b3 * y3_18;
+    fn is_identity(&self) -> Choice {
+        self.z.is_zero()
+    }
 
-    let x3_25 = t4_13 * y3_24; // mul #7
-    let t2_26 = t3_8 * t1_23; // mul #8
-    let x3_27 = t2_26 - x3_25;
+    fn double(&self) -> Self {
+        double_homogeneous(self)
+    }
+}
 
-    let y3_28 = y3_24 * t0_20; // mul #9
-    let t1_29 = t1_23 * z3_22; // mul #10
-    let y3_30 = t1_29 + y3_28;
+impl<C: Rcb15Params> Add<&HomogeneousPoint<C>> for HomogeneousPoint<C> {
+    type Output = Self;
 
-    let t0_31 = t0_20 * t3_8; // mul #11
-    let z3_32 = z3_22 * t4_13; // mul #12
-    let z3_33 = z3_32 + t0_31;
+    fn add(self, rhs: &Self) -> Self {
+        self + *rhs
+    }
+}
 
-    // {
-    //     println!("x, y, z in add {:?} {:?} {:?}", x3, y3, z3);
-    //     let on_curve = y3 * y3 * z3 - x3 * x3 * x3 - Fq::from(3u64) *z3 * z3 *z3;
-    //     println!("on curve: {:?}", on_curve);
-    // }
+impl<C: Rcb15Params> Add for HomogeneousPoint<C> {
+    type Output = Self;
 
-    (x3_27, y3_30, z3_33)
+    fn add(self, rhs: Self) -> Self {
+        let (x, y, z) = formulas::add(self.x, self.y, self.z, rhs.x, rhs.y, rhs.z, C::mul_by_b3);
+        debug_check::<C>(
+            &[(self.x, self.y, self.z), (rhs.x, rhs.y, rhs.z)],
+            (x, y, z),
+        );
+        Self { x, y, z }
+    }
 }
 
-fn core_double<C: CurveExt>(
-    x: C::Base,
-    y: C::Base,
-    z: C::Base,
-    b3: C::Base,
-) -> (C::Base, C::Base, C::Base) {
-    // Algorithm 7 of eprint:2015-1060
-    // Source code from A.3
-    let t0_1 = y * y;
-    let z3_2 = t0_1 + t0_1;
-    let z3_3 = z3_2 + z3_2;
-
-    let z3_4 = z3_3 + z3_3;
-    let t1_5 = y * z;
-    let t2_6 = z * z;
-
-    let t2_7 = b3 * t2_6;
-    let x3_8 = t2_7 * z3_4;
-    let y3_9 = t0_1 + t2_7;
-
-    let z3_10 = t1_5 * z3_4;
-    let t1_11 = t2_7 + t2_7;
-    let t2_12 = t1_11 + t2_7;
-
-    let t0_13 = t0_1 - t2_12;
-    let y3_14 = t0_13 * y3_9;
-    let y3_15 = x3_8 + y3_14;
-
-    let t1_16 = x * y;
-    let x3_17 = t0_13 * t1_16;
-    let x3_18 = x3_17 + x3_17;
-
-    // {
-    //     println!("x, y, z in double {:?} {:?} {:?}", x3, y3, z3);
-    //     let on_curve = y3 * y3 * z3 - x3 * x3 * x3 - Fq::from(3u64) *z3 * z3 *z3;
-    //     println!("on curve: {:?}\n", on_curve);
-    // }
-    (x3_18, y3_15, z3_10)
+impl<C: Rcb15Params> Neg for HomogeneousPoint<C> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self { y: -self.y, ..self }
+    }
+}
+
+impl<C: Rcb15Params> Sub<&HomogeneousPoint<C>> for HomogeneousPoint<C> {
+    type Output = Self;
+
+    fn sub(self, rhs: &Self) -> Self {
+        self - *rhs
+    }
+}
+
+impl<C: Rcb15Params> Sub for HomogeneousPoint<C> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl<C: Rcb15Params> AddAssign for HomogeneousPoint<C> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<C: Rcb15Params> AddAssign<&HomogeneousPoint<C>> for HomogeneousPoint<C> {
+    fn add_assign(&mut self, rhs: &Self) {
+        *self = *self + *rhs;
+    }
+}
+
+impl<C: Rcb15Params> SubAssign for HomogeneousPoint<C> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<C: Rcb15Params> SubAssign<&HomogeneousPoint<C>> for HomogeneousPoint<C> {
+    fn sub_assign(&mut self, rhs: &Self) {
+        *self = *self - *rhs;
+    }
+}
+
+impl<C: Rcb15Params> Sum for HomogeneousPoint<C> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(HomogeneousPoint::identity(), |acc, p| acc + p)
+    }
+}
+
+impl<'a, C: Rcb15Params> Sum<&'a HomogeneousPoint<C>> for HomogeneousPoint<C> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(HomogeneousPoint::identity(), |acc, p| acc + *p)
+    }
+}
+
+pub fn naive_msm<C: Rcb15Params>(points: &[C], scalars: &[C::ScalarExt]) -> C {
+    let mut res = mul(&points[0], &scalars[0]);
+    for (p, s) in points.iter().zip(scalars.iter()).skip(1) {
+        let tmp = mul(p, s);
+        res = add(&res, &tmp)
+    }
+    res
 }
@@ -0,0 +1,246 @@
+//! A halo2 chip that constrains the RCB15 formulas.
+//!
+//! The chip lays out the straight-line programs of [`crate::formulas`] as
+//! they are: every step is one row, with its operands copied into `a` and
+//! `b` and its result in `c`. The circuit therefore cannot drift from the
+//! native code, at the cost of one row per field operation.
+//!
+//! Points are kept in homogeneous coordinates, so neither addition nor
+//! doubling needs an inversion or a case split. The intended use is the
+//! Pasta cycle, e.g. Pallas points in a circuit over the Pallas base field
+//! (the Vesta scalar field), but any a = 0 curve without points of order two
+//! whose base field is the circuit field works.
+
+use halo2_proofs::arithmetic::Field;
+use halo2_proofs::circuit::{AssignedCell, Layouter, Value};
+use halo2_proofs::plonk::{Advice, Column, ConstraintSystem, Error, Fixed, Selector};
+use halo2_proofs::poly::Rotation;
+
+use crate::formulas;
+use crate::ir::{Op, Program};
+
+/// The columns and selectors used by [`Rcb15Chip`].
+#[derive(Clone, Debug)]
+pub struct Rcb15Config {
+    a: Column<Advice>,
+    b: Column<Advice>,
+    c: Column<Advice>,
+    q_add: Selector,
+    q_sub: Selector,
+    q_mul: Selector,
+}
+
+/// A point (X : Y : Z) in homogeneous coordinates, assigned in the circuit.
+#[derive(Clone, Debug)]
+pub struct AssignedPoint<F: Field> {
+    pub x: AssignedCell<F, F>,
+    pub y: AssignedCell<F, F>,
+    pub z: AssignedCell<F, F>,
+}
+
+/// Constrains RCB15 addition and doubling for an a = 0 curve over `F`.
+#[derive(Clone, Debug)]
+pub struct Rcb15Chip<F: Field> {
+    config: Rcb15Config,
+    b3: F,
+}
+
+impl<F: Field> Rcb15Chip<F> {
+    /// `advice` are the `a`, `b` and `c` columns of the field operations;
+    /// `constant` holds `3b` and the other constants of the chip.
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        constant: Column<Fixed>,
+    ) -> Rcb15Config {
+        let [a, b, c] = advice;
+        for column in advice {
+            meta.enable_equality(column);
+        }
+        meta.enable_constant(constant);
+
+        let q_add = meta.selector();
+        let q_sub = meta.selector();
+        let q_mul = meta.selector();
+
+        meta.create_gate("rcb15 field operation", |meta| {
+            let q_add = meta.query_selector(q_add);
+            let q_sub = meta.query_selector(q_sub);
+            let q_mul = meta.query_selector(q_mul);
+            let a = meta.query_advice(a, Rotation::cur());
+            let b = meta.query_advice(b, Rotation::cur());
+            let c = meta.query_advice(c, Rotation::cur());
+
+            vec![
+                q_add * (a.clone() + b.clone() - c.clone()),
+                q_sub * (a.clone() - b.clone() - c.clone()),
+                q_mul * (a * b - c),
+            ]
+        });
+
+        Rcb15Config {
+            a,
+            b,
+            c,
+            q_add,
+            q_sub,
+            q_mul,
+        }
+    }
+
+    /// `b3` is the curve constant `3b`, e.g. [`crate::halo2::Rcb15Params::B3`].
+    pub fn construct(config: Rcb15Config, b3: F) -> Self {
+        Self { config, b3 }
+    }
+
+    /// Witness a point, constraining it to satisfy the curve equation and to
+    /// have Y != 0. On a curve without points of order two this accepts
+    /// exactly the valid homogeneous triples, the identity (0 : 1 : 0)
+    /// included, and rejects (0 : 0 : 0).
+    pub fn witness_point(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: Value<F>,
+        y: Value<F>,
+        z: Value<F>,
+    ) -> Result<AssignedPoint<F>, Error> {
+        let config = &self.config;
+        let point = layouter.assign_region(
+            || "witness point",
+            |mut region| {
+                let x = region.assign_advice(|| "x", config.a, 0, || x)?;
+                let y = region.assign_advice(|| "y", config.b, 0, || y)?;
+                let z = region.assign_advice(|| "z", config.c, 0, || z)?;
+
+                // Y * (1 / Y) = 1
+                config.q_mul.enable(&mut region, 1)?;
+                y.copy_advice(|| "y", &mut region, config.a, 1)?;
+                let y_inv = y.value().map(|y| y.invert().unwrap_or(F::ZERO));
+                region.assign_advice(|| "1 / y", config.b, 1, || y_inv)?;
+                region.assign_advice_from_constant(|| "1", config.c, 1, F::ONE)?;
+
+                Ok(AssignedPoint { x, y, z })
+            },
+        )?;
+
+        let sides = self.run(
+            layouter.namespace(|| "curve equation"),
+            &formulas::CURVE_EQUATION,
+            &[&point.x, &point.y, &point.z],
+        )?;
+        // MockProver cannot locate failures in a region without cells, so
+        // copy one side instead of only constraining the two to be equal
+        layouter.assign_region(
+            || "on curve",
+            |mut region| {
+                let lhs = sides[0].copy_advice(|| "3 y^2 z", &mut region, config.a, 0)?;
+                region.constrain_equal(lhs.cell(), sides[1].cell())
+            },
+        )?;
+
+        Ok(point)
+    }
+
+    /// Complete addition, see [`formulas::add`].
+    pub fn add(
+        &self,
+        layouter: impl Layouter<F>,
+        p1: &AssignedPoint<F>,
+        p2: &AssignedPoint<F>,
+    ) -> Result<AssignedPoint<F>, Error> {
+        let out = self.run(
+            layouter,
+            &formulas::ADD,
+            &[&p1.x, &p1.y, &p1.z, &p2.x, &p2.y, &p2.z],
+        )?;
+        Ok(Self::point(out))
+    }
+
+    /// Doubling, see [`formulas::double`].
+    pub fn double(
+        &self,
+        layouter: impl Layouter<F>,
+        p: &AssignedPoint<F>,
+    ) -> Result<AssignedPoint<F>, Error> {
+        let out = self.run(layouter, &formulas::DOUBLE, &[&p.x, &p.y, &p.z])?;
+        Ok(Self::point(out))
+    }
+
+    fn point(out: Vec<AssignedCell<F, F>>) -> AssignedPoint<F> {
+        let [x, y, z]: [AssignedCell<F, F>; 3] = out.try_into().unwrap();
+        AssignedPoint { x, y, z }
+    }
+
+    /// Lay out `program` in a single region, one row per step, and return
+    /// its outputs. `inputs` are in the order of `program.inputs`.
+    fn run(
+        &self,
+        mut layouter: impl Layouter<F>,
+        program: &Program,
+        inputs: &[&AssignedCell<F, F>],
+    ) -> Result<Vec<AssignedCell<F, F>>, Error> {
+        assert_eq!(inputs.len(), program.inputs.len());
+        let config = &self.config;
+
+        layouter.assign_region(
+            || program.name,
+            |mut region| {
+                let mut values: Vec<(&str, AssignedCell<F, F>)> = program
+                    .inputs
+                    .iter()
+                    .copied()
+                    .zip(inputs.iter().map(|&cell| cell.clone()))
+                    .collect();
+                let get = |values: &[(&str, AssignedCell<F, F>)], name: &str| {
+                    values
+                        .iter()
+                        .rev()
+                        .find(|(n, _)| *n == name)
+                        .map(|(_, cell)| cell.clone())
+                        .unwrap_or_else(|| panic!("{}: undefined value {}", program.name, name))
+                };
+
+                for (offset, step) in program.steps.iter().enumerate() {
+                    // `None` as the second operand stands for the constant 3b
+                    let (selector, lhs, rhs) = match step.op {
+                        Op::Add(a, b) => (config.q_add, a, Some(b)),
+                        Op::Sub(a, b) => (config.q_sub, a, Some(b)),
+                        Op::Mul(a, b) => (config.q_mul, a, Some(b)),
+                        Op::Square(a) => (config.q_mul, a, Some(a)),
+                        Op::MulB3(a) => (config.q_mul, a, None),
+                    };
+
+                    selector.enable(&mut region, offset)?;
+                    let lhs =
+                        get(&values, lhs).copy_advice(|| "a", &mut region, config.a, offset)?;
+                    let rhs = match rhs {
+                        Some(rhs) => {
+                            get(&values, rhs).copy_advice(|| "b", &mut region, config.b, offset)?
+                        }
+                        None => region.assign_advice_from_constant(
+                            || "3b",
+                            config.b,
+                            offset,
+                            self.b3,
+                        )?,
+                    };
+
+                    let operands = lhs.value().zip(rhs.value());
+                    let value = match step.op {
+                        Op::Add(..) => operands.map(|(a, b)| *a + *b),
+                        Op::Sub(..) => operands.map(|(a, b)| *a - *b),
+                        _ => operands.map(|(a, b)| *a * *b),
+                    };
+                    let out = region.assign_advice(|| step.out, config.c, offset, || value)?;
+                    values.push((step.out, out));
+                }
+
+                Ok(program
+                    .outputs
+                    .iter()
+                    .map(|name| get(&values, name))
+                    .collect())
+            },
+        )
+    }
+}
@@ -0,0 +1,196 @@
+//! Hashing to curves with the simplified SWU map, as in RFC 9380.
+//!
+//! An a = 0 curve E has no simplified SWU map of its own, so the map goes to
+//! a curve E' with `A'B' != 0` and back through an isogeny E' -> E (section
+//! 6.6.3 of RFC 9380). Both steps are written here once, on homogeneous
+//! triples and without inversions, like [`crate::formulas`]; the backends
+//! provide the per-curve constants through [`SswuParams`] and combine the two
+//! outputs of `hash_to_curve` with the RCB15 addition.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Neg;
+
+use digest::core_api::BlockSizeUser;
+use digest::Digest;
+use subtle::Choice;
+
+use crate::formulas::Rcb15Field;
+
+/// The field operations used by the map, on top of those of the formulas.
+///
+/// Selections take a [`Choice`] so that the halo2 backend can stay constant
+/// time; the arkworks fields simply branch.
+pub trait SswuField: Rcb15Field + Neg<Output = Self> {
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn ct_is_zero(&self) -> Choice;
+    /// `b` if `choice` is set, `a` otherwise.
+    fn ct_select(a: &Self, b: &Self, choice: Choice) -> Self;
+    /// `sgn0` of section 4.1 of RFC 9380, i.e. the parity of the canonical
+    /// representative for a prime field.
+    fn sgn0(&self) -> Choice;
+    /// The big-endian integer `okm` reduced modulo the field order, as in
+    /// `hash_to_field`.
+    fn from_okm(okm: &[u8]) -> Self;
+}
+
+/// Per-curve constants of a suite `*_XMD:*_SSWU_RO_`.
+pub trait SswuParams {
+    type Field: SswuField + 'static;
+    /// The hash function of `expand_message_xmd`.
+    type Hash: Digest + BlockSizeUser;
+    /// Bytes of uniform output per field element, `L` in RFC 9380.
+    const L: usize;
+    /// The non-square `Z` of the simplified SWU map.
+    const Z: Self::Field;
+    /// The coefficients of the isogenous curve E': y^2 = x^3 + A'x + B'.
+    const ISO_A: Self::Field;
+    const ISO_B: Self::Field;
+    /// The rational maps of the isogeny E' -> E, x = x_num / x_den and
+    /// y = y * y_num / y_den, lowest degree first. The denominators are
+    /// monic, and their leading one is included.
+    const ISO_X_NUM: &'static [Self::Field];
+    const ISO_X_DEN: &'static [Self::Field];
+    const ISO_Y_NUM: &'static [Self::Field];
+    const ISO_Y_DEN: &'static [Self::Field];
+
+    /// `sqrt_ratio(u, v)` of appendix F.2.1 of RFC 9380: `(true, sqrt(u / v))`
+    /// if `u / v` is a square and `(false, sqrt(Z * u / v))` otherwise.
+    fn sqrt_ratio(u: &Self::Field, v: &Self::Field) -> (Choice, Self::Field);
+}
+
+/// `expand_message_xmd` of section 5.3.1 of RFC 9380. A `dst` longer than
+/// 255 bytes is first hashed as in section 5.3.3.
+///
+/// Panics if `len` is more than 255 output blocks of `H`.
+pub fn expand_message_xmd<H: Digest + BlockSizeUser>(
+    msg: &[u8],
+    dst: &[u8],
+    len: usize,
+) -> Vec<u8> {
+    let long_dst;
+    let dst = if dst.len() > 255 {
+        long_dst = H::new()
+            .chain_update(b"H2C-OVERSIZE-DST-")
+            .chain_update(dst)
+            .finalize();
+        long_dst.as_slice()
+    } else {
+        dst
+    };
+    let ell = len.div_ceil(<H as Digest>::output_size());
+    assert!(
+        ell <= 255 && len <= 65535,
+        "expand_message_xmd: len too large"
+    );
+
+    let dst_prime = [dst, &[dst.len() as u8]].concat();
+    let b_0 = H::new()
+        .chain_update(vec![0u8; H::block_size()])
+        .chain_update(msg)
+        .chain_update((len as u16).to_be_bytes())
+        .chain_update([0u8])
+        .chain_update(&dst_prime)
+        .finalize();
+    let mut b_i = H::new()
+        .chain_update(&b_0)
+        .chain_update([1u8])
+        .chain_update(&dst_prime)
+        .finalize();
+
+    let mut uniform_bytes = b_i.to_vec();
+    for i in 2..=ell {
+        let xor: Vec<u8> = b_0.iter().zip(b_i.iter()).map(|(a, b)| a ^ b).collect();
+        b_i = H::new()
+            .chain_update(xor)
+            .chain_update([i as u8])
+            .chain_update(&dst_prime)
+            .finalize();
+        uniform_bytes.extend_from_slice(&b_i);
+    }
+    uniform_bytes.truncate(len);
+    uniform_bytes
+}
+
+/// `hash_to_field(msg, 2)` of section 5.2 of RFC 9380, for a prime field.
+pub fn hash_to_field<S: SswuParams>(msg: &[u8], dst: &[u8]) -> [S::Field; 2] {
+    let uniform_bytes = expand_message_xmd::<S::Hash>(msg, dst, 2 * S::L);
+    [
+        S::Field::from_okm(&uniform_bytes[..S::L]),
+        S::Field::from_okm(&uniform_bytes[S::L..]),
+    ]
+}
+
+/// The simplified SWU map to E', the straight-line program of appendix F.2
+/// of RFC 9380. The final division by `x_den` is left to the homogeneous
+/// coordinates: the result is (x_num : y * x_den : x_den).
+pub fn map_to_curve_simple_swu<S: SswuParams>(u: &S::Field) -> (S::Field, S::Field, S::Field) {
+    let one = S::Field::one();
+
+    let tv1 = S::Z * u.square();
+    let tv2 = tv1.square() + tv1;
+    let tv3 = S::ISO_B * (tv2 + one);
+    // tv2 is zero for at most a handful of u, where x1 = B' / (Z A')
+    let tv4 = S::ISO_A * S::Field::ct_select(&S::Z, &-tv2, !tv2.ct_is_zero());
+    let tv6 = tv4.square();
+    let gx_num = (tv3.square() + S::ISO_A * tv6) * tv3 + S::ISO_B * tv6 * tv4;
+    let gx_den = tv6 * tv4;
+
+    let (is_gx1_square, y1) = S::sqrt_ratio(&gx_num, &gx_den);
+    let x_num = S::Field::ct_select(&(tv1 * tv3), &tv3, is_gx1_square);
+    let y = S::Field::ct_select(&(tv1 * *u * y1), &y1, is_gx1_square);
+    let y = S::Field::ct_select(&-y, &y, !(u.sgn0() ^ y.sgn0()));
+
+    (x_num, y * tv4, tv4)
+}
+
+/// The polynomial with coefficients `coeffs`, homogenised to `degree` and
+/// evaluated at (x : z), i.e. the sum of `c_i x^i z^(degree - i)`.
+fn eval_homogeneous<F: SswuField>(coeffs: &[F], x: F, z: F, degree: usize) -> F {
+    let mut res = F::zero();
+    let mut z_power = F::one();
+    for i in (0..=degree).rev() {
+        res = res * x;
+        if let Some(c) = coeffs.get(i) {
+            res = res + *c * z_power;
+        }
+        z_power = z_power * z;
+    }
+    res
+}
+
+/// The isogeny E' -> E on homogeneous coordinates. With the maps homogenised
+/// to a common degree, (x : y : z) goes to
+/// (x_num y_den z : y y_num x_den : x_den y_den z).
+///
+/// The kernel of the isogeny, where both denominators vanish, goes to the
+/// identity (0 : 1 : 0).
+pub fn iso_map<S: SswuParams>(
+    (x, y, z): (S::Field, S::Field, S::Field),
+) -> (S::Field, S::Field, S::Field) {
+    let x_degree = S::ISO_X_NUM.len().max(S::ISO_X_DEN.len()) - 1;
+    let y_degree = S::ISO_Y_NUM.len().max(S::ISO_Y_DEN.len()) - 1;
+    let x_num = eval_homogeneous(S::ISO_X_NUM, x, z, x_degree);
+    let x_den = eval_homogeneous(S::ISO_X_DEN, x, z, x_degree);
+    let y_num = eval_homogeneous(S::ISO_Y_NUM, x, z, y_degree);
+    let y_den = eval_homogeneous(S::ISO_Y_DEN, x, z, y_degree);
+
+    let y_den_z = y_den * z;
+    let x3 = x_num * y_den_z;
+    let y3 = y * y_num * x_den;
+    let z3 = x_den * y_den_z;
+
+    let in_kernel = z3.ct_is_zero();
+    (
+        S::Field::ct_select(&x3, &S::Field::zero(), in_kernel),
+        S::Field::ct_select(&y3, &S::Field::one(), in_kernel),
+        z3,
+    )
+}
+
+/// `map_to_curve` of RFC 9380: the simplified SWU map followed by the
+/// isogeny, as a homogeneous triple of E.
+pub fn map_to_curve<S: SswuParams>(u: &S::Field) -> (S::Field, S::Field, S::Field) {
+    iso_map::<S>(map_to_curve_simple_swu::<S>(u))
+}
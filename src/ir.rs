@@ -0,0 +1,215 @@
+//! A straight-line program representation of the formulas.
+//!
+//! Each formula in [`crate::formulas`] is written once, as a list of SSA
+//! steps, and the `straight_line!` macro turns that list into both the Rust
+//! function used by the backends and a [`Program`] value. The program can be
+//! interpreted over any [`Rcb15Field`], counted, or exported to a circuit.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::formulas::Rcb15Field;
+use crate::opcount::OpCount;
+
+/// A single field operation. Operands name earlier steps or inputs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Op {
+    Add(&'static str, &'static str),
+    Sub(&'static str, &'static str),
+    Mul(&'static str, &'static str),
+    Square(&'static str),
+    /// Multiplication by the curve constant `3b`.
+    MulB3(&'static str),
+}
+
+impl Op {
+    pub fn operands(&self) -> Vec<&'static str> {
+        match *self {
+            Op::Add(a, b) | Op::Sub(a, b) | Op::Mul(a, b) => vec![a, b],
+            Op::Square(a) | Op::MulB3(a) => vec![a],
+        }
+    }
+}
+
+/// `add(a, b)`, as in the source of the formulas.
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Op::Add(a, b) => write!(f, "add({}, {})", a, b),
+            Op::Sub(a, b) => write!(f, "sub({}, {})", a, b),
+            Op::Mul(a, b) => write!(f, "mul({}, {})", a, b),
+            Op::Square(a) => write!(f, "square({})", a),
+            Op::MulB3(a) => write!(f, "mul_b3({})", a),
+        }
+    }
+}
+
+/// `out = op`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Step {
+    pub out: &'static str,
+    pub op: Op,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Program {
+    pub name: &'static str,
+    pub inputs: &'static [&'static str],
+    pub steps: &'static [Step],
+    pub outputs: &'static [&'static str],
+}
+
+impl Program {
+    /// Evaluate the program; `inputs` are in the order of `self.inputs`.
+    pub fn eval<F: Rcb15Field>(&self, inputs: &[F], mul_by_b3: impl Fn(&F) -> F) -> Vec<F> {
+        let values = self.eval_all(inputs, mul_by_b3);
+        self.outputs
+            .iter()
+            .map(|name| self.get(&values, name))
+            .collect()
+    }
+
+    /// The inputs followed by the value of every step, under their names.
+    pub(crate) fn eval_all<F: Rcb15Field>(
+        &self,
+        inputs: &[F],
+        mul_by_b3: impl Fn(&F) -> F,
+    ) -> Vec<(&'static str, F)> {
+        assert_eq!(inputs.len(), self.inputs.len());
+        let mut values: Vec<(&'static str, F)> = self
+            .inputs
+            .iter()
+            .copied()
+            .zip(inputs.iter().copied())
+            .collect();
+        for step in self.steps {
+            let get = |name| self.get(&values, name);
+            let v = match step.op {
+                Op::Add(a, b) => get(a) + get(b),
+                Op::Sub(a, b) => get(a) - get(b),
+                Op::Mul(a, b) => get(a) * get(b),
+                Op::Square(a) => get(a).square(),
+                Op::MulB3(a) => mul_by_b3(&get(a)),
+            };
+            values.push((step.out, v));
+        }
+        values
+    }
+
+    /// The latest value named `name`.
+    fn get<F: Copy>(&self, values: &[(&'static str, F)], name: &str) -> F {
+        values
+            .iter()
+            .rev()
+            .find(|(n, _)| *n == name)
+            .map(|(_, v)| *v)
+            .unwrap_or_else(|| panic!("{}: undefined value {}", self.name, name))
+    }
+
+    /// Count the operations without evaluating anything.
+    pub fn op_count(&self) -> OpCount {
+        let mut count = OpCount::default();
+        for step in self.steps {
+            match step.op {
+                Op::Add(..) => count.add += 1,
+                Op::Sub(..) => count.sub += 1,
+                Op::Mul(..) => count.mul += 1,
+                Op::Square(..) => count.square += 1,
+                Op::MulB3(..) => count.mul_by_const += 1,
+            }
+        }
+        count
+    }
+
+    /// Check that the program is in SSA form: every name is defined once,
+    /// before it is used, and every output is defined.
+    pub fn validate(&self) -> Result<(), String> {
+        let mut defined: Vec<&str> = Vec::new();
+        for &input in self.inputs {
+            if defined.contains(&input) {
+                return Err(format!("{}: {} defined twice", self.name, input));
+            }
+            defined.push(input);
+        }
+        for step in self.steps {
+            for operand in step.op.operands() {
+                if !defined.contains(&operand) {
+                    return Err(format!("{}: {} used before definition", self.name, operand));
+                }
+            }
+            if defined.contains(&step.out) {
+                return Err(format!("{}: {} defined twice", self.name, step.out));
+            }
+            defined.push(step.out);
+        }
+        for output in self.outputs {
+            if !defined.contains(output) {
+                return Err(format!("{}: output {} is not defined", self.name, output));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Define a formula as a list of SSA steps, generating a generic function
+/// over [`Rcb15Field`] and a [`Program`] constant from the same source.
+///
+/// ```ignore
+/// straight_line! {
+///     /// Doubling.
+///     pub fn double, const DOUBLE = (x, y, z) -> (x3, y3, z3) {
+///         t0 = square(y);
+///         ...
+///     }
+/// }
+/// ```
+macro_rules! straight_line {
+    (
+        $(#[$attr: meta])*
+        pub fn $name: ident, const $program: ident =
+            ($($input: ident),*) -> ($($output: ident),*) {
+                $($out: ident = $op: ident($($arg: ident),*);)*
+            }
+    ) => {
+        $(#[$attr])*
+        #[allow(clippy::too_many_arguments)]
+        #[inline]
+        pub fn $name<F: $crate::formulas::Rcb15Field>(
+            $($input: F,)*
+            mul_by_b3: impl Fn(&F) -> F,
+        ) -> ($(straight_line!(@field $output F)),*) {
+            $(let $out = straight_line!(@expr mul_by_b3 $op $($arg)*);)*
+            ($($output),*)
+        }
+
+        $(#[$attr])*
+        pub const $program: $crate::ir::Program = $crate::ir::Program {
+            name: stringify!($name),
+            inputs: &[$(stringify!($input)),*],
+            steps: &[$($crate::ir::Step {
+                out: stringify!($out),
+                op: straight_line!(@op $op $($arg)*),
+            }),*],
+            outputs: &[$(stringify!($output)),*],
+        };
+    };
+
+    (@field $output: ident $f: ident) => { $f };
+
+    (@expr $m: ident add $a: ident $b: ident) => { $a + $b };
+    (@expr $m: ident sub $a: ident $b: ident) => { $a - $b };
+    (@expr $m: ident mul $a: ident $b: ident) => { $a * $b };
+    (@expr $m: ident square $a: ident) => { $a.square() };
+    (@expr $m: ident mul_b3 $a: ident) => { $m(&$a) };
+
+    (@op add $a: ident $b: ident) => { $crate::ir::Op::Add(stringify!($a), stringify!($b)) };
+    (@op sub $a: ident $b: ident) => { $crate::ir::Op::Sub(stringify!($a), stringify!($b)) };
+    (@op mul $a: ident $b: ident) => { $crate::ir::Op::Mul(stringify!($a), stringify!($b)) };
+    (@op square $a: ident) => { $crate::ir::Op::Square(stringify!($a)) };
+    (@op mul_b3 $a: ident) => { $crate::ir::Op::MulB3(stringify!($a)) };
+}
+
+pub(crate) use straight_line;
@@ -1,5 +1,31 @@
+//! RCB15 complete formulas for a = 0 short Weierstrass curves.
+//!
+//! The formulas, the arkworks backend, hashing to curves and `opcount` only
+//! need `core` and `alloc`. The default `std` feature adds the halo2
+//! backend, since halo2curves needs `std`, and the counting wrapper of
+//! `opcount` (`opcount::count`, `Counted` and `arkworks::op_count`), which
+//! keeps its counts in a thread local.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 pub mod arkworks;
+#[cfg(feature = "std")]
+pub mod convert;
+pub mod formulas;
+#[cfg(feature = "std")]
 pub mod halo2;
+#[cfg(feature = "halo2-chip")]
+pub mod halo2_chip;
+pub mod hash_to_curve;
+pub mod ir;
+pub mod opcount;
+pub mod params;
+#[cfg(feature = "r1cs-gadget")]
+pub mod r1cs_gadget;
+#[cfg(feature = "trace")]
+pub mod trace;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests;
@@ -0,0 +1,305 @@
+//! Field-operation counts for the RCB15 formulas.
+//!
+//! [`Counted`] wraps a field element and records every operation applied to
+//! it; [`count`] runs one of the formulas in [`crate::formulas`] over it.
+//! The formulas are straight-line code, so the counts do not depend on the
+//! input values.
+//!
+//! [`circuit_cost`] gives the same counts in terms of circuit cost, for
+//! choosing between RCB15 and incomplete formulas in a circuit.
+
+#[cfg(feature = "std")]
+use core::cell::Cell;
+use core::fmt;
+use core::ops::Add;
+#[cfg(feature = "std")]
+use core::ops::{Mul, Sub};
+
+use crate::formulas;
+#[cfg(feature = "std")]
+use crate::formulas::Rcb15Field;
+use crate::ir::{Op, Program, Step};
+
+/// The formulas that can be counted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Formula {
+    Add,
+    AddMixed,
+    Double,
+}
+
+impl Formula {
+    pub const ALL: [Formula; 3] = [Formula::Add, Formula::AddMixed, Formula::Double];
+
+    pub fn program(self) -> Program {
+        match self {
+            Formula::Add => formulas::ADD,
+            Formula::AddMixed => formulas::ADD_MIXED,
+            Formula::Double => formulas::DOUBLE,
+        }
+    }
+
+    /// The algorithm of eprint:2015-1060 that this formula implements.
+    pub fn algorithm(self) -> usize {
+        match self {
+            Formula::Add => 7,
+            Formula::AddMixed => 8,
+            Formula::Double => 9,
+        }
+    }
+
+    /// Number of projective input points; the second operand of the mixed
+    /// addition is affine.
+    fn projective_inputs(self) -> usize {
+        match self {
+            Formula::Add => 2,
+            Formula::AddMixed | Formula::Double => 1,
+        }
+    }
+}
+
+/// The coordinates that the points of a formula are given in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Coordinates {
+    /// (X : Y : Z), as used by the formulas and the arkworks backend.
+    Homogeneous,
+    /// (X : Y : Z) standing for (X/Z^2, Y/Z^3), as stored by halo2curves.
+    /// The projective inputs and the output are converted, see
+    /// [`crate::halo2::jacobian_to_homogeneous`].
+    Jacobian,
+}
+
+impl Coordinates {
+    pub const ALL: [Coordinates; 2] = [Coordinates::Homogeneous, Coordinates::Jacobian];
+}
+
+/// Number of each field operation performed by a formula.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OpCount {
+    pub mul: usize,
+    pub square: usize,
+    pub add: usize,
+    pub sub: usize,
+    /// Multiplications by a curve constant, i.e. by `3b`.
+    pub mul_by_const: usize,
+}
+
+/// Uses the notation of the paper, where `a` counts both additions and
+/// subtractions, e.g. `12M + 0S + 2m_b3 + 19a` for Algorithm 7.
+impl fmt::Display for OpCount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}M + {}S + {}m_b3 + {}a",
+            self.mul,
+            self.square,
+            self.mul_by_const,
+            self.add + self.sub
+        )
+    }
+}
+
+/// The cost of a formula as a circuit over the base field.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CircuitCost {
+    /// Multiplications and squarings of two variables.
+    pub nonlinear: usize,
+    /// Additions and subtractions.
+    pub linear: usize,
+    /// Multiplications by a constant, i.e. by `3b`.
+    pub constant: usize,
+}
+
+impl CircuitCost {
+    /// In R1CS only the nonlinear multiplications need a constraint; linear
+    /// combinations and multiplications by constants are free.
+    pub fn r1cs_constraints(&self) -> usize {
+        self.nonlinear
+    }
+
+    /// A PLONK arithmetisation with one field operation per gate, as in
+    /// `halo2_chip`, needs a gate for every operation.
+    pub fn plonk_gates(&self) -> usize {
+        self.nonlinear + self.linear + self.constant
+    }
+}
+
+impl From<OpCount> for CircuitCost {
+    fn from(count: OpCount) -> Self {
+        CircuitCost {
+            nonlinear: count.mul + count.square,
+            linear: count.add + count.sub,
+            constant: count.mul_by_const,
+        }
+    }
+}
+
+impl Add for CircuitCost {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        CircuitCost {
+            nonlinear: self.nonlinear + rhs.nonlinear,
+            linear: self.linear + rhs.linear,
+            constant: self.constant + rhs.constant,
+        }
+    }
+}
+
+impl fmt::Display for CircuitCost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} nonlinear + {} linear + {} constant",
+            self.nonlinear, self.linear, self.constant
+        )
+    }
+}
+
+/// The field operations of [`crate::halo2::jacobian_to_homogeneous`],
+/// leaving out the selection for the identity.
+pub const JACOBIAN_TO_HOMOGENEOUS: Program = Program {
+    name: "jacobian_to_homogeneous",
+    inputs: &["x", "y", "z"],
+    steps: &[
+        Step {
+            out: "x_1",
+            op: Op::Mul("x", "z"),
+        },
+        Step {
+            out: "t_2",
+            op: Op::Square("z"),
+        },
+        Step {
+            out: "z_3",
+            op: Op::Mul("t_2", "z"),
+        },
+    ],
+    outputs: &["x_1", "y", "z_3"],
+};
+
+/// The field operations of [`crate::halo2::homogeneous_to_jacobian`].
+pub const HOMOGENEOUS_TO_JACOBIAN: Program = Program {
+    name: "homogeneous_to_jacobian",
+    inputs: &["x", "y", "z"],
+    steps: &[
+        Step {
+            out: "x_1",
+            op: Op::Mul("x", "z"),
+        },
+        Step {
+            out: "t_2",
+            op: Op::Square("z"),
+        },
+        Step {
+            out: "y_3",
+            op: Op::Mul("y", "t_2"),
+        },
+    ],
+    outputs: &["x_1", "y_3", "z"],
+};
+
+/// The circuit cost of `formula` with its points in `coordinates`, traced
+/// from its straight-line program.
+pub fn circuit_cost(formula: Formula, coordinates: Coordinates) -> CircuitCost {
+    let cost = CircuitCost::from(formula.program().op_count());
+    match coordinates {
+        Coordinates::Homogeneous => cost,
+        Coordinates::Jacobian => {
+            let to_homogeneous = CircuitCost::from(JACOBIAN_TO_HOMOGENEOUS.op_count());
+            let to_jacobian = CircuitCost::from(HOMOGENEOUS_TO_JACOBIAN.op_count());
+            (0..formula.projective_inputs())
+                .fold(cost + to_jacobian, |cost, _| cost + to_homogeneous)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+thread_local! {
+    static COUNT: Cell<OpCount> = Cell::new(OpCount::default());
+}
+
+/// Increment the counter selected by `op`.
+#[cfg(feature = "std")]
+fn record(op: impl FnOnce(&mut OpCount) -> &mut usize) {
+    COUNT.with(|count| {
+        let mut c = count.get();
+        *op(&mut c) += 1;
+        count.set(c);
+    });
+}
+
+/// A field element that counts the operations applied to it. The counter is
+/// thread-local, so this needs the `std` feature.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Counted<F>(pub F);
+
+#[cfg(feature = "std")]
+impl<F: Rcb15Field> Counted<F> {
+    /// Multiply by a curve constant with `f`, counted as one `m_b3`
+    /// whatever `f` does internally.
+    pub fn mul_by_const(&self, f: impl Fn(&F) -> F) -> Self {
+        record(|c| &mut c.mul_by_const);
+        Counted(f(&self.0))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<F: Rcb15Field> Add for Counted<F> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        record(|c| &mut c.add);
+        Counted(self.0 + rhs.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<F: Rcb15Field> Sub for Counted<F> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        record(|c| &mut c.sub);
+        Counted(self.0 - rhs.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<F: Rcb15Field> Mul for Counted<F> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        record(|c| &mut c.mul);
+        Counted(self.0 * rhs.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<F: Rcb15Field> Rcb15Field for Counted<F> {
+    fn square(&self) -> Self {
+        record(|c| &mut c.square);
+        Counted(self.0.square())
+    }
+}
+
+/// Count the operations of `formula`, evaluated on inputs all equal to `x`.
+#[cfg(feature = "std")]
+pub fn count<F: Rcb15Field>(formula: Formula, x: F, mul_by_b3: impl Fn(&F) -> F) -> OpCount {
+    let x = Counted(x);
+    let mul_by_b3 = |v: &Counted<F>| v.mul_by_const(&mul_by_b3);
+
+    COUNT.with(|count| count.set(OpCount::default()));
+    match formula {
+        Formula::Add => {
+            formulas::add(x, x, x, x, x, x, mul_by_b3);
+        }
+        Formula::AddMixed => {
+            formulas::add_mixed(x, x, x, x, x, mul_by_b3);
+        }
+        Formula::Double => {
+            formulas::double(x, x, x, mul_by_b3);
+        }
+    }
+    COUNT.with(|count| count.get())
+}
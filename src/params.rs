@@ -0,0 +1,22 @@
+/// Shape of the curve coefficient `a`, which selects the RCB15 formula family.
+///
+/// RCB15 gives three sets of complete formulas: Algorithms 1-3 for a generic
+/// `a`, Algorithms 4-6 for `a = -3` and Algorithms 7-9 for `a = 0`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FormulaClass {
+    AZero,
+    AMinusThree,
+    Generic,
+}
+
+/// How membership in the prime-order subgroup is decided for a curve.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubgroupCheck {
+    /// The cofactor is one: every point on the curve is in the subgroup.
+    CofactorOne,
+    /// Use the GLV endomorphism, as in Scott's `phi(P) = [-x^2]P` test.
+    Endomorphism,
+    /// Use the untwist-Frobenius-twist endomorphism, as in Scott's
+    /// `psi(P) = [x]P` test for G2 of a BLS12 curve.
+    Frobenius,
+}
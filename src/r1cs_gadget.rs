@@ -0,0 +1,267 @@
+//! An R1CS gadget for the RCB15 formulas, built on ark-r1cs-std.
+//!
+//! [`ProjectiveVar`] keeps a point in homogeneous coordinates as variables
+//! of the base field Fq of the curve, so the constraint system is over Fq:
+//! BLS12-377 G1 points are constrained in a BW6-761 circuit, whose scalar
+//! field is the BLS12-377 Fq. The gadget cannot be used in a Groth16 circuit
+//! over BLS12-377, which is over the BLS12-377 Fr; the curve embedded there
+//! is Ed-on-BLS12-377, whose short Weierstrass form has a != 0 and needs the
+//! generic-a formulas (RCB15 Algorithm 1), which this crate does not have.
+//! The formulas are interpreted from their straight-line programs (see
+//! [`crate::ir`]): additions, subtractions and multiplications by `3b` are
+//! linear and cost no constraints, every other multiplication costs one.
+//!
+//! As with the native code, the formulas are complete only on curves without
+//! points of order two. BLS12-377 G1 has points of order two on the full
+//! curve, and P + (P + T) gives (0 : 0 : 0) for such a T, so witnesses and
+//! inputs of a curve with a cofactor are constrained to the prime-order
+//! subgroup.
+
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::marker::PhantomData;
+
+use ark_ec::short_weierstrass_jacobian::GroupProjective;
+use ark_ff::{BigInteger, One, PrimeField};
+use ark_r1cs_std::alloc::{AllocVar, AllocationMode};
+use ark_r1cs_std::boolean::Boolean;
+use ark_r1cs_std::eq::EqGadget;
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_r1cs_std::fields::FieldVar;
+use ark_r1cs_std::select::CondSelectGadget;
+use ark_r1cs_std::R1CSVar;
+use ark_relations::ns;
+use ark_relations::r1cs::{ConstraintSystem, ConstraintSystemRef, Namespace, SynthesisError};
+
+use crate::arkworks::Rcb15Params;
+use crate::formulas;
+use crate::ir::{Op, Program};
+use crate::opcount::Formula;
+use crate::params::SubgroupCheck;
+
+/// A point (X : Y : Z) in homogeneous coordinates, as circuit variables.
+///
+/// Its native value is a `GroupProjective` holding the homogeneous triple,
+/// the same convention as [`crate::arkworks`].
+#[derive(Clone, Debug)]
+pub struct ProjectiveVar<P: Rcb15Params>
+where
+    P::BaseField: PrimeField,
+{
+    pub x: FpVar<P::BaseField>,
+    pub y: FpVar<P::BaseField>,
+    pub z: FpVar<P::BaseField>,
+    _params: PhantomData<P>,
+}
+
+impl<P: Rcb15Params> ProjectiveVar<P>
+where
+    P::BaseField: PrimeField,
+{
+    pub fn new(x: FpVar<P::BaseField>, y: FpVar<P::BaseField>, z: FpVar<P::BaseField>) -> Self {
+        Self {
+            x,
+            y,
+            z,
+            _params: PhantomData,
+        }
+    }
+
+    /// The identity (0 : 1 : 0), as constants.
+    pub fn identity() -> Self {
+        Self::new(FpVar::zero(), FpVar::one(), FpVar::zero())
+    }
+
+    /// Complete addition, see [`formulas::add`].
+    pub fn add(&self, other: &Self) -> Result<Self, SynthesisError> {
+        let inputs = [&self.x, &self.y, &self.z, &other.x, &other.y, &other.z];
+        Ok(Self::from_outputs(eval::<P>(&formulas::ADD, &inputs)?))
+    }
+
+    /// Doubling, see [`formulas::double`].
+    pub fn double(&self) -> Result<Self, SynthesisError> {
+        let out = eval::<P>(&formulas::DOUBLE, &[&self.x, &self.y, &self.z])?;
+        Ok(Self::from_outputs(out))
+    }
+
+    /// Double-and-add over all of `bits`, which are little-endian. The
+    /// number of constraints depends only on the number of bits.
+    pub fn scalar_mul_le<'a>(
+        &self,
+        bits: impl Iterator<Item = &'a Boolean<P::BaseField>>,
+    ) -> Result<Self, SynthesisError> {
+        let bits: Vec<_> = bits.collect();
+        let mut res = Self::identity();
+        for bit in bits.into_iter().rev() {
+            res = res.double()?;
+            let sum = res.add(self)?;
+            res = Self::conditionally_select(bit, &sum, &res)?;
+        }
+        Ok(res)
+    }
+
+    /// Enforce the curve equation Y^2 Z = X^3 + b Z^3 and Y != 0. On a curve
+    /// without points of order two this admits exactly the valid points,
+    /// the identity included.
+    fn enforce_on_curve(&self) -> Result<(), SynthesisError> {
+        let sides = eval::<P>(&formulas::CURVE_EQUATION, &[&self.x, &self.y, &self.z])?;
+        sides[0].enforce_equal(&sides[1])?;
+        self.y.enforce_not_equal(&FpVar::zero())
+    }
+
+    /// Enforce Scott's subgroup test (eprint:2021-1130), phi(P) = [lambda]P
+    /// with phi(X : Y : Z) = (beta X : Y : Z), as `phi(P) + [-lambda]P = O`,
+    /// i.e. X = 0, Z = 0 and Y != 0. On BLS12 G1, `-ENDO_LAMBDA` = x^2 has
+    /// half the bits of r, and phi is linear, so this costs a doubling and
+    /// an addition per bit of x^2. A point with a component outside the
+    /// subgroup ends elsewhere or at (0 : 0 : 0), which has Y = 0.
+    fn enforce_in_subgroup(&self) -> Result<(), SynthesisError> {
+        let bits: Vec<_> = (-P::ENDO_LAMBDA)
+            .into_repr()
+            .to_bits_le()
+            .into_iter()
+            .map(Boolean::constant)
+            .collect();
+        let phi = Self::new(
+            self.x.clone() * P::ENDO_BETA,
+            self.y.clone(),
+            self.z.clone(),
+        );
+        let res = phi.add(&self.scalar_mul_le(bits.iter())?)?;
+        res.x.enforce_equal(&FpVar::zero())?;
+        res.z.enforce_equal(&FpVar::zero())?;
+        res.y.enforce_not_equal(&FpVar::zero())
+    }
+
+    fn from_outputs(out: Vec<FpVar<P::BaseField>>) -> Self {
+        let [x, y, z]: [FpVar<P::BaseField>; 3] = out.try_into().unwrap();
+        Self::new(x, y, z)
+    }
+}
+
+/// Evaluate `program` over circuit variables; `inputs` are in the order of
+/// `program.inputs`.
+fn eval<P: Rcb15Params>(
+    program: &Program,
+    inputs: &[&FpVar<P::BaseField>],
+) -> Result<Vec<FpVar<P::BaseField>>, SynthesisError>
+where
+    P::BaseField: PrimeField,
+{
+    assert_eq!(inputs.len(), program.inputs.len());
+    let mut values: Vec<(&str, FpVar<P::BaseField>)> = program
+        .inputs
+        .iter()
+        .copied()
+        .zip(inputs.iter().map(|&v| v.clone()))
+        .collect();
+    let get = |values: &[(&str, FpVar<P::BaseField>)], name: &str| {
+        values
+            .iter()
+            .rev()
+            .find(|(n, _)| *n == name)
+            .map(|(_, v)| v.clone())
+            .unwrap_or_else(|| panic!("{}: undefined value {}", program.name, name))
+    };
+
+    for step in program.steps {
+        let v = match step.op {
+            Op::Add(a, b) => get(&values, a) + get(&values, b),
+            Op::Sub(a, b) => get(&values, a) - get(&values, b),
+            Op::Mul(a, b) => get(&values, a) * get(&values, b),
+            Op::Square(a) => get(&values, a).square()?,
+            Op::MulB3(a) => get(&values, a) * P::B3,
+        };
+        values.push((step.out, v));
+    }
+    Ok(program
+        .outputs
+        .iter()
+        .map(|name| get(&values, name))
+        .collect())
+}
+
+impl<P: Rcb15Params> R1CSVar<P::BaseField> for ProjectiveVar<P>
+where
+    P::BaseField: PrimeField,
+{
+    type Value = GroupProjective<P>;
+
+    fn cs(&self) -> ConstraintSystemRef<P::BaseField> {
+        self.x.cs().or(self.y.cs()).or(self.z.cs())
+    }
+
+    fn value(&self) -> Result<Self::Value, SynthesisError> {
+        Ok(GroupProjective::new(
+            self.x.value()?,
+            self.y.value()?,
+            self.z.value()?,
+        ))
+    }
+}
+
+impl<P: Rcb15Params> CondSelectGadget<P::BaseField> for ProjectiveVar<P>
+where
+    P::BaseField: PrimeField,
+{
+    fn conditionally_select(
+        cond: &Boolean<P::BaseField>,
+        true_value: &Self,
+        false_value: &Self,
+    ) -> Result<Self, SynthesisError> {
+        Ok(Self::new(
+            FpVar::conditionally_select(cond, &true_value.x, &false_value.x)?,
+            FpVar::conditionally_select(cond, &true_value.y, &false_value.y)?,
+            FpVar::conditionally_select(cond, &true_value.z, &false_value.z)?,
+        ))
+    }
+}
+
+/// Allocates the homogeneous triple of a `GroupProjective`, as produced by
+/// [`crate::arkworks::add`] and friends. Witnesses and inputs are checked to
+/// be on the curve and, unless the cofactor is one, to be in the prime-order
+/// subgroup. Constants are not checked.
+impl<P: Rcb15Params> AllocVar<GroupProjective<P>, P::BaseField> for ProjectiveVar<P>
+where
+    P::BaseField: PrimeField,
+{
+    fn new_variable<T: Borrow<GroupProjective<P>>>(
+        cs: impl Into<Namespace<P::BaseField>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let ns = cs.into();
+        let cs = ns.cs();
+        let p = f().map(|p| *p.borrow());
+
+        let x = FpVar::new_variable(ns!(cs, "x"), || p.map(|p| p.x), mode)?;
+        let y = FpVar::new_variable(ns!(cs, "y"), || p.map(|p| p.y), mode)?;
+        let z = FpVar::new_variable(ns!(cs, "z"), || p.map(|p| p.z), mode)?;
+        let point = Self::new(x, y, z);
+
+        if mode != AllocationMode::Constant {
+            point.enforce_on_curve()?;
+            if P::SUBGROUP_CHECK != SubgroupCheck::CofactorOne {
+                point.enforce_in_subgroup()?;
+            }
+        }
+        Ok(point)
+    }
+}
+
+/// Number of R1CS constraints of `formula` on witness inputs.
+pub fn num_constraints<P: Rcb15Params>(formula: Formula) -> usize
+where
+    P::BaseField: PrimeField,
+{
+    let program = formula.program();
+    let cs = ConstraintSystem::<P::BaseField>::new_ref();
+    let inputs = program
+        .inputs
+        .iter()
+        .map(|_| FpVar::new_witness(cs.clone(), || Ok(P::BaseField::one())))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    eval::<P>(&program, &inputs.iter().collect::<Vec<_>>()).unwrap();
+    cs.num_constraints()
+}
@@ -1,8 +1,16 @@
 use crate::arkworks::add;
+use crate::arkworks::add_mixed;
 use crate::arkworks::double;
+use crate::arkworks::homogeneous_eq;
 use crate::arkworks::homogeneous_form_to_affine;
+use crate::arkworks::homogeneous_identity;
+use crate::arkworks::is_on_curve_homogeneous;
+use crate::arkworks::jacobian_to_homogeneous;
 use crate::arkworks::mul;
 use crate::arkworks::naive_msm;
+use crate::arkworks::Rcb15Params;
+use crate::impl_ark_rcb15_exceptional_tests;
+use crate::impl_ark_rcb15_proptests;
 use crate::impl_ark_rcb15_tests;
 use ark_bls12_377::g1::Parameters;
 use ark_ec::msm::VariableBaseMSM;
@@ -10,10 +18,37 @@ use ark_ec::short_weierstrass_jacobian::GroupProjective;
 use ark_ec::AffineCurve;
 use ark_ec::ModelParameters;
 use ark_ec::ProjectiveCurve;
+use ark_ec::SWModelParameters;
 use ark_ff::PrimeField;
+use ark_ff::Zero;
 use ark_std::test_rng;
 use ark_std::UniformRand;
 
 const REPEAT: usize = 5;
 
 impl_ark_rcb15_tests!(Parameters);
+impl_ark_rcb15_exceptional_tests!(Parameters);
+impl_ark_rcb15_proptests!(Parameters);
+
+#[test]
+fn test_double_two_torsion() {
+    // The full BLS12-377 G1 curve has even order, so T = (-1, 0) is a point
+    // of order two: y^2 = x^3 + 1 vanishes at x = -1. Doubling it must give O.
+    let one = <Parameters as ModelParameters>::BaseField::from(1u64);
+    let t = GroupProjective::<Parameters>::new(-one, Zero::zero(), one);
+    let lhs = t.y * t.y * t.z;
+    let rhs = t.x * t.x * t.x + Parameters::COEFF_B * t.z * t.z * t.z;
+    assert_eq!(lhs, rhs);
+
+    let o = homogeneous_identity::<Parameters>();
+    assert!(homogeneous_eq(&double(&t), &o));
+    assert!(homogeneous_eq(&add(&t, &t), &o));
+
+    // RCB15 is only complete when E(Fq) has no point of order two (Theorem 1
+    // of eprint:2015-1060). T + O is one of the exceptions: the formula
+    // returns the invalid triple (0 : 0 : 0) rather than T. Points in the
+    // prime-order subgroup never hit this case.
+    let res = add(&t, &o);
+    assert!(res.x.is_zero() && res.y.is_zero() && res.z.is_zero());
+    assert!(!homogeneous_form_to_affine(&t).is_in_correct_subgroup_assuming_on_curve());
+}
@@ -1,8 +1,16 @@
 use crate::arkworks::add;
+use crate::arkworks::add_mixed;
 use crate::arkworks::double;
+use crate::arkworks::homogeneous_eq;
 use crate::arkworks::homogeneous_form_to_affine;
+use crate::arkworks::homogeneous_identity;
+use crate::arkworks::is_on_curve_homogeneous;
+use crate::arkworks::jacobian_to_homogeneous;
 use crate::arkworks::mul;
 use crate::arkworks::naive_msm;
+use crate::arkworks::Rcb15Params;
+use crate::impl_ark_rcb15_exceptional_tests;
+use crate::impl_ark_rcb15_proptests;
 use crate::impl_ark_rcb15_tests;
 use ark_bls12_381::g1::Parameters;
 use ark_ec::msm::VariableBaseMSM;
@@ -10,6 +18,7 @@ use ark_ec::short_weierstrass_jacobian::GroupProjective;
 use ark_ec::AffineCurve;
 use ark_ec::ModelParameters;
 use ark_ec::ProjectiveCurve;
+use ark_ec::SWModelParameters;
 use ark_ff::PrimeField;
 use ark_std::test_rng;
 use ark_std::UniformRand;
@@ -17,3 +26,5 @@ use ark_std::UniformRand;
 const REPEAT: usize = 5;
 
 impl_ark_rcb15_tests!(Parameters);
+impl_ark_rcb15_exceptional_tests!(Parameters);
+impl_ark_rcb15_proptests!(Parameters);
@@ -0,0 +1,216 @@
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ff::{One, UniformRand, Zero};
+use ark_std::test_rng;
+use halo2curves::bn256;
+use halo2curves::group::ff::Field;
+use halo2curves::group::Group;
+use halo2curves::pasta::{pallas, vesta};
+
+use crate::arkworks::{self, homogeneous_eq, homogeneous_identity};
+use crate::halo2::{self, mul_homogeneous, mul_homogeneous_randomized, HomogeneousPoint};
+
+const REPEAT: usize = 5;
+
+fn check_arkworks_representation<P: arkworks::Rcb15Params>() {
+    let mut rng = test_rng();
+    for _ in 0..REPEAT {
+        let p = arkworks::HomogeneousPoint::<P>::rand_with_random_z(&mut rng);
+        let mut q = p;
+        q.randomize_representation(&mut rng);
+        assert_ne!(q.0.z, p.0.z);
+        assert_eq!(q, p);
+        assert_eq!(q.to_affine(), p.to_affine());
+    }
+
+    // the identity stays the identity
+    let mut o = arkworks::HomogeneousPoint::<P>::identity();
+    o.randomize_representation(&mut rng);
+    assert_eq!(o, arkworks::HomogeneousPoint::identity());
+}
+
+#[test]
+fn test_arkworks_randomize_representation() {
+    check_arkworks_representation::<ark_bn254::g1::Parameters>();
+    check_arkworks_representation::<ark_bls12_381::g1::Parameters>();
+    check_arkworks_representation::<ark_bls12_381::g2::Parameters>();
+    check_arkworks_representation::<ark_bls12_377::g1::Parameters>();
+    check_arkworks_representation::<ark_bls12_377::g2::Parameters>();
+}
+
+fn check_arkworks_mul<P: arkworks::Rcb15Params>() {
+    let mut rng = test_rng();
+    for _ in 0..REPEAT {
+        let p = arkworks::HomogeneousPoint::<P>::rand_with_random_z(&mut rng);
+        let k = P::ScalarField::rand(&mut rng);
+        let expected = arkworks::mul(&p.0, &k);
+        let randomized = arkworks::mul_randomized(&p, &k, &mut rng);
+        assert!(arkworks::homogeneous_eq(&randomized.0, &expected));
+        assert_eq!(randomized.to_affine(), p.to_affine().mul(k).into_affine());
+        // a different run, a different representation of the same point
+        let again = arkworks::mul_randomized(&p, &k, &mut rng);
+        assert_ne!(again.0.z, randomized.0.z);
+        assert_eq!(again, randomized);
+    }
+
+    // zero and one, and the identity as a base
+    let p = arkworks::HomogeneousPoint::<P>::rand(&mut rng);
+    let o = arkworks::HomogeneousPoint::<P>::identity();
+    let k = P::ScalarField::rand(&mut rng);
+    assert_eq!(
+        arkworks::mul_randomized(&p, &P::ScalarField::zero(), &mut rng),
+        o
+    );
+    assert_eq!(
+        arkworks::mul_randomized(&p, &P::ScalarField::one(), &mut rng),
+        p
+    );
+    assert_eq!(arkworks::mul_randomized(&o, &k, &mut rng), o);
+}
+
+#[test]
+fn test_arkworks_mul_randomized() {
+    check_arkworks_mul::<ark_bn254::g1::Parameters>();
+    check_arkworks_mul::<ark_bls12_381::g1::Parameters>();
+    check_arkworks_mul::<ark_bls12_381::g2::Parameters>();
+    check_arkworks_mul::<ark_bls12_377::g1::Parameters>();
+    check_arkworks_mul::<ark_bls12_377::g2::Parameters>();
+}
+
+fn check_halo2_representation<C>()
+where
+    C: halo2::Rcb15Params,
+    HomogeneousPoint<C>: Group<Scalar = C::ScalarExt>,
+{
+    let mut rng = test_rng();
+    for _ in 0..REPEAT {
+        let p = <HomogeneousPoint<C> as Group>::random(&mut rng);
+        let mut q = p;
+        q.randomize_representation(&mut rng);
+        assert_ne!(q.z, p.z);
+        assert_eq!(q, p);
+        assert_eq!(q.to_curve().to_affine(), p.to_curve().to_affine());
+        assert!(bool::from(halo2::is_in_subgroup(&q)));
+    }
+
+    let mut o = HomogeneousPoint::<C>::identity();
+    o.randomize_representation(&mut rng);
+    assert!(bool::from(o.is_identity()));
+    assert_eq!(o, HomogeneousPoint::identity());
+}
+
+fn check_halo2_mul<C>()
+where
+    C: halo2::Rcb15Params,
+    HomogeneousPoint<C>: Group<Scalar = C::ScalarExt>,
+{
+    let mut rng = test_rng();
+    for _ in 0..REPEAT {
+        let p = HomogeneousPoint::<C>::random_with_random_z(&mut rng);
+        let k = C::ScalarExt::random(&mut rng);
+        let expected = mul_homogeneous(&p, &k);
+        let blinded = mul_homogeneous_randomized(&p, &k, &mut rng);
+        assert_eq!(blinded, expected);
+        assert_eq!(
+            blinded.to_curve().to_affine(),
+            (p.to_curve() * k).to_affine()
+        );
+        // a different run, a different representation of the same point
+        let again = mul_homogeneous_randomized(&p, &k, &mut rng);
+        assert_ne!(again.z, blinded.z);
+        assert_eq!(again, blinded);
+    }
+
+    // zero and one, and the identity as a base
+    let p = <HomogeneousPoint<C> as Group>::random(&mut rng);
+    let o = HomogeneousPoint::<C>::identity();
+    let k = C::ScalarExt::random(&mut rng);
+    assert_eq!(
+        mul_homogeneous_randomized(&p, &C::ScalarExt::ZERO, &mut rng),
+        o
+    );
+    assert_eq!(
+        mul_homogeneous_randomized(&p, &C::ScalarExt::ONE, &mut rng),
+        p
+    );
+    assert_eq!(mul_homogeneous_randomized(&o, &k, &mut rng), o);
+}
+
+#[test]
+fn test_halo2_randomize_representation() {
+    check_halo2_representation::<bn256::G1>();
+    check_halo2_representation::<pallas::Point>();
+    check_halo2_representation::<vesta::Point>();
+}
+
+#[test]
+fn test_halo2_mul_randomized() {
+    check_halo2_mul::<bn256::G1>();
+    check_halo2_mul::<pallas::Point>();
+    check_halo2_mul::<vesta::Point>();
+}
+
+fn check_arkworks_blinded<P: arkworks::Rcb15Params>() {
+    let mut rng = test_rng();
+    for _ in 0..REPEAT {
+        let p = arkworks::HomogeneousPoint::<P>::rand(&mut rng).0;
+        let k = P::ScalarField::rand(&mut rng);
+        let expected = arkworks::mul(&p, &k);
+        assert!(homogeneous_eq(
+            &arkworks::mul_blinded(&p, &k, &mut rng),
+            &expected
+        ));
+    }
+
+    // the largest scalar, zero and the identity
+    let p = arkworks::HomogeneousPoint::<P>::rand(&mut rng).0;
+    let minus_one = -P::ScalarField::one();
+    assert!(homogeneous_eq(
+        &arkworks::mul_blinded(&p, &minus_one, &mut rng),
+        &-p
+    ));
+    assert!(homogeneous_eq(
+        &arkworks::mul_blinded(&p, &P::ScalarField::zero(), &mut rng),
+        &homogeneous_identity()
+    ));
+    assert!(homogeneous_eq(
+        &arkworks::mul_blinded(&homogeneous_identity::<P>(), &minus_one, &mut rng),
+        &homogeneous_identity()
+    ));
+}
+
+#[test]
+fn test_arkworks_mul_blinded() {
+    check_arkworks_blinded::<ark_bn254::g1::Parameters>();
+    check_arkworks_blinded::<ark_bls12_381::g1::Parameters>();
+    check_arkworks_blinded::<ark_bls12_381::g2::Parameters>();
+    check_arkworks_blinded::<ark_bls12_377::g1::Parameters>();
+    check_arkworks_blinded::<ark_bls12_377::g2::Parameters>();
+}
+
+fn check_halo2_blinded<C: halo2::Rcb15Params>() {
+    let mut rng = test_rng();
+    for _ in 0..REPEAT {
+        let p = C::random(&mut rng);
+        let k = C::ScalarExt::random(&mut rng);
+        assert_eq!(halo2::mul_blinded(&p, &k, &mut rng), halo2::mul(&p, &k));
+    }
+
+    let p = C::random(&mut rng);
+    let minus_one = -C::ScalarExt::ONE;
+    assert_eq!(halo2::mul_blinded(&p, &minus_one, &mut rng), -p);
+    assert_eq!(
+        halo2::mul_blinded(&p, &C::ScalarExt::ZERO, &mut rng),
+        C::identity()
+    );
+    assert_eq!(
+        halo2::mul_blinded(&C::identity(), &minus_one, &mut rng),
+        C::identity()
+    );
+}
+
+#[test]
+fn test_halo2_mul_blinded() {
+    check_halo2_blinded::<bn256::G1>();
+    check_halo2_blinded::<pallas::Point>();
+    check_halo2_blinded::<vesta::Point>();
+}
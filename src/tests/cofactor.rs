@@ -0,0 +1,98 @@
+use ark_ec::bls12::Bls12Parameters;
+use ark_ec::short_weierstrass_jacobian::GroupProjective;
+use ark_ec::{AffineCurve, ProjectiveCurve, SWModelParameters};
+use ark_ff::{FpParameters, One, PrimeField, UniformRand, Zero};
+use ark_std::test_rng;
+
+use super::subgroup::outside_subgroup;
+use crate::arkworks::{
+    affine_to_homogeneous_form, homogeneous_eq, homogeneous_form_to_affine, homogeneous_identity,
+    is_in_subgroup, jacobian_to_homogeneous, Rcb15Params,
+};
+
+const REPEAT: usize = 5;
+
+type Bn254 = ark_bn254::g1::Parameters;
+type Bls12_381G1 = ark_bls12_381::g1::Parameters;
+type Bls12_381G2 = ark_bls12_381::g2::Parameters;
+type Bls12_377G1 = ark_bls12_377::g1::Parameters;
+type Bls12_377G2 = ark_bls12_377::g2::Parameters;
+
+/// The signed parameter x of a BLS12 curve, as a scalar.
+fn x<B: Bls12Parameters, F: PrimeField>() -> F {
+    let x = F::from(B::X[0]);
+    if B::X_IS_NEGATIVE {
+        -x
+    } else {
+        x
+    }
+}
+
+/// `P::clear_cofactor` is `[h_eff]P`, i.e. `[h_eff / h]` times the cofactor
+/// clearing of arkworks, which multiplies by the cofactor `h`.
+fn check<P: Rcb15Params>(h_eff_over_h: P::ScalarField) {
+    let mut rng = test_rng();
+    let points = (0..REPEAT)
+        .map(|_| affine_to_homogeneous_form(&GroupProjective::<P>::rand(&mut rng).into_affine()));
+
+    for p in points.chain(outside_subgroup::<P>(REPEAT)) {
+        let res = P::clear_cofactor(&p);
+        assert!(is_in_subgroup(&res));
+
+        let expected = homogeneous_form_to_affine(&p)
+            .scale_by_cofactor()
+            .mul(h_eff_over_h.into_repr());
+        assert!(homogeneous_eq(&res, &jacobian_to_homogeneous(&expected)));
+    }
+
+    let o = homogeneous_identity::<P>();
+    assert!(homogeneous_eq(&P::clear_cofactor(&o), &o));
+}
+
+#[test]
+fn test_clear_cofactor() {
+    type Fr381 = ark_bls12_381::Fr;
+    type Fr377 = ark_bls12_377::Fr;
+
+    // the cofactor of BN254 G1 is one
+    let mut rng = test_rng();
+    let p = affine_to_homogeneous_form(&GroupProjective::<Bn254>::rand(&mut rng).into_affine());
+    assert!(homogeneous_eq(&Bn254::clear_cofactor(&p), &p));
+
+    // G1: h_eff = 1 - x
+    let h_eff = Fr381::one() - x::<ark_bls12_381::Parameters, Fr381>();
+    check::<Bls12_381G1>(h_eff * Bls12_381G1::COFACTOR_INV);
+    let h_eff = Fr377::one() - x::<ark_bls12_377::Parameters, Fr377>();
+    check::<Bls12_377G1>(h_eff * Bls12_377G1::COFACTOR_INV);
+
+    // G2: h_eff = 3 (x^2 - 1) h
+    let x381 = x::<ark_bls12_381::Parameters, Fr381>();
+    check::<Bls12_381G2>(Fr381::from(3u64) * (x381 * x381 - Fr381::one()));
+    let x377 = x::<ark_bls12_377::Parameters, Fr377>();
+    check::<Bls12_377G2>(Fr377::from(3u64) * (x377 * x377 - Fr377::one()));
+}
+
+/// On BLS12-377 G1, points of order dividing the cofactor reach (0 : 0 : 0)
+/// in the formulas; clearing the cofactor must still give the identity.
+#[test]
+fn test_clear_cofactor_torsion() {
+    type Fq = ark_bls12_377::Fq;
+    type Fr = ark_bls12_377::Fr;
+    let o = homogeneous_identity::<Bls12_377G1>();
+    let check = |p: &GroupProjective<Bls12_377G1>| {
+        let res = Bls12_377G1::clear_cofactor(p);
+        assert!(homogeneous_eq(&res, &o));
+        assert!(!res.y.is_zero());
+        assert!(is_in_subgroup(&res));
+    };
+
+    // T = (-1 : 0 : 1) has order two
+    check(&GroupProjective::new(-Fq::one(), Fq::zero(), Fq::one()));
+
+    // [r]Q has order dividing h
+    for q in outside_subgroup::<Bls12_377G1>(REPEAT) {
+        let modulus = <Fr as PrimeField>::Params::MODULUS;
+        let t = homogeneous_form_to_affine(&q).mul(modulus);
+        check(&jacobian_to_homogeneous(&t));
+    }
+}
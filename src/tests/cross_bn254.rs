@@ -0,0 +1,122 @@
+use ark_ec::AffineCurve;
+use ark_ff::Zero;
+use ark_std::test_rng;
+use halo2curves::bn256::Fr;
+use halo2curves::bn256::G1Affine;
+use halo2curves::bn256::G1;
+use halo2curves::group::ff::Field;
+use halo2curves::group::prime::PrimeCurveAffine;
+use halo2curves::group::{Curve, Group};
+
+use crate::arkworks;
+use crate::convert::fr_ark_to_halo2;
+use crate::convert::fr_halo2_to_ark;
+use crate::convert::g1_ark_to_halo2;
+use crate::convert::g1_halo2_to_ark;
+use crate::halo2;
+
+const REPEAT: usize = 5;
+
+fn to_ark(p: &G1) -> ark_bn254::G1Projective {
+    g1_halo2_to_ark(&p.to_affine()).into_projective()
+}
+
+#[test]
+fn test_conversion() {
+    let mut rng = test_rng();
+
+    assert_eq!(
+        g1_halo2_to_ark(&G1Affine::generator()),
+        ark_bn254::G1Affine::prime_subgroup_generator()
+    );
+    assert_eq!(
+        g1_halo2_to_ark(&G1Affine::identity()),
+        ark_bn254::G1Affine::zero()
+    );
+    assert_eq!(
+        g1_ark_to_halo2(&ark_bn254::G1Affine::zero()),
+        G1Affine::identity()
+    );
+
+    for _ in 0..REPEAT {
+        let x = G1::random(&mut rng).to_affine();
+        assert_eq!(g1_ark_to_halo2(&g1_halo2_to_ark(&x)), x);
+
+        let s = Fr::random(&mut rng);
+        assert_eq!(fr_ark_to_halo2(&fr_halo2_to_ark(&s)), s);
+    }
+}
+
+#[test]
+fn test_add() {
+    let mut rng = test_rng();
+
+    for _ in 0..REPEAT {
+        let x = G1::random(&mut rng);
+        let y = G1::random(&mut rng);
+
+        let res = halo2::add(&x, &y);
+        let res2 = arkworks::add(&to_ark(&x), &to_ark(&y));
+
+        assert_eq!(
+            g1_halo2_to_ark(&halo2::homogeneous_form_to_affine(&res)),
+            arkworks::homogeneous_form_to_affine(&res2)
+        );
+    }
+}
+
+#[test]
+fn test_double() {
+    let mut rng = test_rng();
+
+    for _ in 0..REPEAT {
+        let x = G1::random(&mut rng);
+
+        let res = halo2::double(&x);
+        let res2 = arkworks::double(&to_ark(&x));
+
+        assert_eq!(
+            g1_halo2_to_ark(&halo2::homogeneous_form_to_affine(&res)),
+            arkworks::homogeneous_form_to_affine(&res2)
+        );
+    }
+}
+
+#[test]
+fn test_mul() {
+    let mut rng = test_rng();
+
+    for _ in 0..REPEAT {
+        let base = G1::random(&mut rng);
+        let scalar = Fr::random(&mut rng);
+
+        let res = halo2::mul(&base, &scalar);
+        let res2 = arkworks::mul(&to_ark(&base), &fr_halo2_to_ark(&scalar));
+
+        assert_eq!(
+            g1_halo2_to_ark(&halo2::homogeneous_form_to_affine(&res)),
+            arkworks::homogeneous_form_to_affine(&res2)
+        );
+    }
+}
+
+#[test]
+fn test_msm() {
+    let mut rng = test_rng();
+
+    for i in 1..REPEAT {
+        let dim = 1 << i;
+        let bases: Vec<_> = (0..dim).map(|_| G1::random(&mut rng)).collect();
+        let scalars: Vec<_> = (0..dim).map(|_| Fr::random(&mut rng)).collect();
+        let bases_ark: Vec<_> = bases.iter().map(to_ark).collect();
+        let scalars_ark: Vec<_> = scalars.iter().map(fr_halo2_to_ark).collect();
+
+        let res = halo2::naive_msm(&bases, &scalars);
+        let res2 = arkworks::naive_msm(&bases_ark, &scalars_ark);
+
+        assert_eq!(
+            g1_halo2_to_ark(&halo2::homogeneous_form_to_affine(&res)),
+            arkworks::homogeneous_form_to_affine(&res2)
+        );
+    }
+}
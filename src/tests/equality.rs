@@ -0,0 +1,96 @@
+use ark_ec::short_weierstrass_jacobian::GroupProjective;
+use ark_ec::ProjectiveCurve;
+use ark_ff::{One, UniformRand, Zero};
+use ark_std::test_rng;
+use halo2curves::group::ff::Field;
+use halo2curves::{bn256, pasta::pallas, pasta::vesta};
+use subtle::ConstantTimeEq;
+
+use crate::arkworks::{
+    self, affine_to_homogeneous_form, homogeneous_eq, homogeneous_identity, jacobian_to_homogeneous,
+};
+use crate::halo2::{self, HomogeneousPoint};
+
+const REPEAT: usize = 5;
+
+fn check_arkworks<P: arkworks::Rcb15Params>() {
+    let mut rng = test_rng();
+    let o = homogeneous_identity::<P>();
+    let scale = |p: &GroupProjective<P>, s: P::BaseField| {
+        GroupProjective::<P>::new(p.x * s, p.y * s, p.z * s)
+    };
+
+    // every (0 : Y : 0) is the identity
+    let s = P::BaseField::rand(&mut rng);
+    assert!(homogeneous_eq(&o, &scale(&o, s)));
+    assert!(arkworks::HomogeneousPoint(o) == arkworks::HomogeneousPoint(scale(&o, s)));
+
+    for _ in 0..REPEAT {
+        let jacobian = GroupProjective::<P>::rand(&mut rng);
+        let p = affine_to_homogeneous_form(&jacobian.into_affine());
+        let q = affine_to_homogeneous_form(&GroupProjective::<P>::rand(&mut rng).into_affine());
+        let p2 = scale(&p, P::BaseField::rand(&mut rng));
+
+        assert!(homogeneous_eq(&p, &p2));
+        assert!(homogeneous_eq(&p, &jacobian_to_homogeneous(&jacobian)));
+        assert!(!homogeneous_eq(&p, &q));
+        assert!(!homogeneous_eq(&p, &GroupProjective::new(p.x, -p.y, p.z)));
+        assert!(!homogeneous_eq(&p, &o));
+        assert!(!homogeneous_eq(&o, &p2));
+        assert!(arkworks::HomogeneousPoint(p) == arkworks::HomogeneousPoint(p2));
+        assert!(arkworks::HomogeneousPoint(p) != arkworks::HomogeneousPoint(q));
+    }
+
+    // the identity of arkworks is the Jacobian (1, 1, 0)
+    assert!(homogeneous_eq(
+        &jacobian_to_homogeneous(&GroupProjective::<P>::zero()),
+        &o
+    ));
+    let one = P::BaseField::one();
+    assert!(!homogeneous_eq(
+        &GroupProjective::<P>::new(one, one, one),
+        &GroupProjective::new(one, one, P::BaseField::zero())
+    ));
+}
+
+fn check_halo2<C: halo2::Rcb15Params>() {
+    let mut rng = test_rng();
+    let o = HomogeneousPoint::<C>::identity();
+    let scale = |p: &HomogeneousPoint<C>, s: C::Base| HomogeneousPoint::<C> {
+        x: p.x * s,
+        y: p.y * s,
+        z: p.z * s,
+    };
+
+    let s = C::Base::random(&mut rng);
+    assert!(bool::from(o.ct_eq(&scale(&o, s))));
+    assert_eq!(o, HomogeneousPoint::from(C::identity()));
+
+    for _ in 0..REPEAT {
+        let p = HomogeneousPoint::from(C::random(&mut rng));
+        let q = HomogeneousPoint::from(C::random(&mut rng));
+        let p2 = scale(&p, C::Base::random(&mut rng));
+
+        assert!(bool::from(p.ct_eq(&p2)));
+        assert!(!bool::from(p.ct_eq(&q)));
+        assert!(!bool::from(p.ct_eq(&HomogeneousPoint { y: -p.y, ..p })));
+        assert!(!bool::from(p.ct_eq(&o)));
+        assert!(!bool::from(o.ct_eq(&p2)));
+        assert_eq!(p, p2);
+        assert_ne!(p, q);
+    }
+}
+
+#[test]
+fn test_arkworks() {
+    check_arkworks::<ark_bn254::g1::Parameters>();
+    check_arkworks::<ark_bls12_381::g1::Parameters>();
+    check_arkworks::<ark_bls12_377::g1::Parameters>();
+}
+
+#[test]
+fn test_halo2() {
+    check_halo2::<bn256::G1>();
+    check_halo2::<pallas::Point>();
+    check_halo2::<vesta::Point>();
+}
@@ -0,0 +1,221 @@
+use ark_std::test_rng;
+use halo2_proofs::circuit::{Layouter, SimpleFloorPlanner, Value};
+use halo2_proofs::dev::MockProver;
+use halo2_proofs::plonk::{Circuit, Column, ConstraintSystem, Error, Instance};
+use halo2curves::group::ff::Field;
+use halo2curves::group::Group;
+use halo2curves::pasta::pallas::{Base, Point};
+
+use crate::formulas;
+use crate::halo2::{self, homogeneous_to_jacobian, jacobian_to_homogeneous, Rcb15Params};
+use crate::halo2_chip::{Rcb15Chip, Rcb15Config};
+
+const K: u32 = 8;
+const REPEAT: usize = 5;
+
+#[derive(Clone, Copy, Debug)]
+enum ChipOp {
+    Add,
+    Double,
+}
+
+/// Witnesses `p` (and `q` for additions), applies the chip and exposes the
+/// coordinates of the result as the public input.
+#[derive(Clone, Debug)]
+struct TestCircuit {
+    op: ChipOp,
+    p: [Value<Base>; 3],
+    q: [Value<Base>; 3],
+}
+
+impl Circuit<Base> for TestCircuit {
+    type Config = (Rcb15Config, Column<Instance>);
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            op: self.op,
+            p: [Value::unknown(); 3],
+            q: [Value::unknown(); 3],
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Base>) -> Self::Config {
+        let advice = [
+            meta.advice_column(),
+            meta.advice_column(),
+            meta.advice_column(),
+        ];
+        let constant = meta.fixed_column();
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        (Rcb15Chip::configure(meta, advice, constant), instance)
+    }
+
+    fn synthesize(
+        &self,
+        (config, instance): Self::Config,
+        mut layouter: impl Layouter<Base>,
+    ) -> Result<(), Error> {
+        let chip = Rcb15Chip::construct(config, Point::B3);
+
+        let [x, y, z] = self.p;
+        let p = chip.witness_point(layouter.namespace(|| "p"), x, y, z)?;
+        let res = match self.op {
+            ChipOp::Add => {
+                let [x, y, z] = self.q;
+                let q = chip.witness_point(layouter.namespace(|| "q"), x, y, z)?;
+                chip.add(layouter.namespace(|| "p + q"), &p, &q)?
+            }
+            ChipOp::Double => chip.double(layouter.namespace(|| "2p"), &p)?,
+        };
+
+        layouter.constrain_instance(res.x.cell(), instance, 0)?;
+        layouter.constrain_instance(res.y.cell(), instance, 1)?;
+        layouter.constrain_instance(res.z.cell(), instance, 2)
+    }
+}
+
+fn known((x, y, z): (Base, Base, Base)) -> [Value<Base>; 3] {
+    [Value::known(x), Value::known(y), Value::known(z)]
+}
+
+fn prove(
+    op: ChipOp,
+    p: (Base, Base, Base),
+    q: (Base, Base, Base),
+    out: (Base, Base, Base),
+) -> bool {
+    let circuit = TestCircuit {
+        op,
+        p: known(p),
+        q: known(q),
+    };
+    let prover = MockProver::run(K, &circuit, vec![vec![out.0, out.1, out.2]]).unwrap();
+    prover.verify().is_ok()
+}
+
+/// Run the chip on `p + q` and check it against the native formulas, which
+/// are in turn checked against `expected`.
+fn check_add(p: (Base, Base, Base), q: (Base, Base, Base), expected: Point) {
+    let out = formulas::add(p.0, p.1, p.2, q.0, q.1, q.2, Point::mul_by_b3);
+    assert_eq!(to_point(out), expected);
+    assert!(prove(ChipOp::Add, p, q, out));
+}
+
+fn check_double(p: (Base, Base, Base), expected: Point) {
+    let out = formulas::double(p.0, p.1, p.2, Point::mul_by_b3);
+    assert_eq!(to_point(out), expected);
+    assert!(prove(ChipOp::Double, p, p, out));
+}
+
+fn to_point((x, y, z): (Base, Base, Base)) -> Point {
+    let (x, y, z) = homogeneous_to_jacobian::<Point>(x, y, z);
+    Point::new_jacobian(x, y, z).unwrap()
+}
+
+/// The same point with every coordinate scaled by a random non-zero `s`.
+fn rescale((x, y, z): (Base, Base, Base), s: Base) -> (Base, Base, Base) {
+    (x * s, y * s, z * s)
+}
+
+#[test]
+fn test_add() {
+    let mut rng = test_rng();
+
+    for _ in 0..REPEAT {
+        let p = Point::random(&mut rng);
+        let q = Point::random(&mut rng);
+        let (hp, hq) = (jacobian_to_homogeneous(&p), jacobian_to_homogeneous(&q));
+
+        check_add(hp, hq, p + q);
+        assert_eq!(halo2::add(&p, &q), p + q);
+    }
+}
+
+#[test]
+fn test_add_exceptional() {
+    let mut rng = test_rng();
+    let o = jacobian_to_homogeneous(&Point::identity());
+
+    for _ in 0..REPEAT {
+        let p = Point::random(&mut rng);
+        let hp = jacobian_to_homogeneous(&p);
+
+        // P + P, P + O, O + P, P + (-P)
+        check_add(hp, hp, p.double());
+        check_add(hp, o, p);
+        check_add(o, hp, p);
+        check_add(hp, jacobian_to_homogeneous(&-p), Point::identity());
+    }
+    check_add(o, o, Point::identity());
+}
+
+#[test]
+fn test_double() {
+    let mut rng = test_rng();
+
+    for _ in 0..REPEAT {
+        let p = Point::random(&mut rng);
+
+        check_double(jacobian_to_homogeneous(&p), p.double());
+        assert_eq!(halo2::double(&p), p.double());
+    }
+    check_double(
+        jacobian_to_homogeneous(&Point::identity()),
+        Point::identity(),
+    );
+}
+
+#[test]
+fn test_non_normalized_inputs() {
+    let mut rng = test_rng();
+
+    for _ in 0..REPEAT {
+        let p = Point::random(&mut rng);
+        let q = Point::random(&mut rng);
+        let hp = rescale(jacobian_to_homogeneous(&p), Base::random(&mut rng));
+        let hq = rescale(jacobian_to_homogeneous(&q), Base::random(&mut rng));
+        let o = rescale((Base::ZERO, Base::ONE, Base::ZERO), Base::random(&mut rng));
+
+        check_add(hp, hq, p + q);
+        check_add(hp, o, p);
+        check_double(hp, p.double());
+        check_double(o, Point::identity());
+    }
+}
+
+#[test]
+fn test_rejects_invalid() {
+    let mut rng = test_rng();
+    let p = Point::random(&mut rng);
+    let q = Point::random(&mut rng);
+    let (hp, hq) = (jacobian_to_homogeneous(&p), jacobian_to_homogeneous(&q));
+    let out = formulas::add(hp.0, hp.1, hp.2, hq.0, hq.1, hq.2, Point::mul_by_b3);
+
+    // a wrong result
+    let wrong = jacobian_to_homogeneous(&(p + q + q));
+    assert!(!prove(ChipOp::Add, hp, hq, wrong));
+
+    // the same result in a different representation is a different
+    // public input
+    assert!(!prove(ChipOp::Add, hp, hq, rescale(out, Base::from(2))));
+
+    // a point off the curve
+    let off_curve = (hp.0, hp.1 + Base::ONE, hp.2);
+    let out = formulas::add(
+        off_curve.0,
+        off_curve.1,
+        off_curve.2,
+        hq.0,
+        hq.1,
+        hq.2,
+        Point::mul_by_b3,
+    );
+    assert!(!prove(ChipOp::Add, off_curve, hq, out));
+
+    // (0 : 0 : 0) satisfies the curve equation but is not a point
+    let zero = (Base::ZERO, Base::ZERO, Base::ZERO);
+    assert!(!prove(ChipOp::Double, zero, zero, zero));
+}
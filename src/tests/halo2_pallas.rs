@@ -7,14 +7,47 @@ use halo2curves::group::{Curve, Group};
 use halo2curves::pasta::pallas::Point;
 use halo2curves::pasta::pallas::Scalar;
 use halo2curves::CurveAffine;
+use halo2curves::CurveExt;
 
 use crate::halo2::add;
+use crate::halo2::add_mixed;
 use crate::halo2::double;
 use crate::halo2::homogeneous_form_to_affine;
+use crate::halo2::is_on_curve_homogeneous;
+use crate::halo2::jacobian_to_homogeneous;
 use crate::halo2::mul;
 use crate::halo2::naive_msm;
+use crate::halo2::HomogeneousPoint;
+use crate::halo2::Rcb15Params;
+use crate::impl_halo2_rcb15_exceptional_tests;
+use crate::impl_halo2_rcb15_proptests;
 
 const REPEAT: usize = 5;
+#[test]
+fn test_params() {
+    let mut rng = test_rng();
+
+    assert_eq!(Point::B3, Point::b() + Point::b() + Point::b());
+
+    // (x, y) -> (beta * x, y) is multiplication by lambda
+    for _ in 0..REPEAT {
+        let x = Point::random(&mut rng);
+        let (x1, y1, z1) = x.jacobian_coordinates();
+        let endo = Point::new_jacobian(x1 * Point::ENDO_BETA, y1, z1).unwrap();
+        assert_eq!(endo, x.mul(Point::ENDO_LAMBDA));
+    }
+}
+
+#[test]
+fn test_mul_by_b3() {
+    let mut rng = test_rng();
+
+    for _ in 0..REPEAT {
+        let x = <Point as CurveExt>::Base::random(&mut rng);
+        assert_eq!(Point::mul_by_b3(&x), Point::B3 * x);
+    }
+}
+
 #[test]
 fn test_add() {
     let mut rng = test_rng();
@@ -25,8 +58,9 @@ fn test_add() {
         // test additions
         let z = x + y;
         let res = add(&x, &y);
-        let res_affine = homogeneous_form_to_affine(&res);
-        assert_eq!(res_affine.is_on_curve().unwrap_u8(), 1);
+        assert!(bool::from(is_on_curve_homogeneous(
+            &HomogeneousPoint::from(res)
+        )));
         #[cfg(debug_assertions)]
         {
             println!("halo2: {:?}", z.to_affine());
@@ -37,6 +71,23 @@ fn test_add() {
     }
 }
 
+#[test]
+fn test_add_mixed() {
+    let mut rng = test_rng();
+
+    for _ in 0..REPEAT {
+        let x = Point::random(&mut rng);
+        let y = Point::random(&mut rng);
+        let y_affine = y.to_affine();
+
+        assert_eq!(add_mixed(&x, &y_affine), x + y);
+        // P + P, P + (-P) and P + O through the mixed formula
+        assert_eq!(add_mixed(&y, &y_affine), y.double());
+        assert!(bool::from(add_mixed(&-y, &y_affine).is_identity()));
+        assert_eq!(add_mixed(&x, &Point::identity().to_affine()), x);
+    }
+}
+
 #[test]
 fn test_double() {
     let mut rng = test_rng();
@@ -131,7 +182,7 @@ fn multiexp_serial<C: CurveAffine>(coeffs: &[C::Scalar], bases: &[C], acc: &mut
 
         let mut tmp = u64::from_le_bytes(v);
         tmp >>= skip_bits - (skip_bytes * 8);
-        tmp = tmp % (1 << c);
+        tmp %= 1 << c;
 
         tmp as usize
     }
@@ -169,7 +220,7 @@ fn multiexp_serial<C: CurveAffine>(coeffs: &[C::Scalar], bases: &[C], acc: &mut
                         other += a;
                         other
                     }
-                    Bucket::Projective(a) => other + &a,
+                    Bucket::Projective(a) => other + a,
                 }
             }
         }
@@ -190,7 +241,10 @@ fn multiexp_serial<C: CurveAffine>(coeffs: &[C::Scalar], bases: &[C], acc: &mut
         let mut running_sum = C::Curve::identity();
         for exp in buckets.into_iter().rev() {
             running_sum = exp.add(running_sum);
-            *acc = *acc + &running_sum;
+            *acc += &running_sum;
         }
     }
 }
+
+impl_halo2_rcb15_exceptional_tests!(Point);
+impl_halo2_rcb15_proptests!(Point, Scalar);
@@ -0,0 +1,181 @@
+use ark_bls12_381::g1::Parameters as Bls12_381G1;
+use ark_bls12_381::Fq;
+use ark_ec::short_weierstrass_jacobian::GroupProjective;
+use ark_ff::{One, PrimeField, Zero};
+use halo2curves::group::ff::Field;
+use halo2curves::pasta::{pallas, vesta};
+use halo2curves::CurveExt;
+use sha2::{Digest, Sha256};
+
+use crate::arkworks::{self, homogeneous_eq, homogeneous_identity, is_in_subgroup};
+use crate::halo2::{self, HomogeneousPoint};
+use crate::hash_to_curve::{
+    expand_message_xmd, hash_to_field, iso_map, map_to_curve, map_to_curve_simple_swu,
+};
+
+fn from_hex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn fq(s: &str) -> Fq {
+    Fq::from_be_bytes_mod_order(&from_hex(s))
+}
+
+fn affine(x: &str, y: &str) -> GroupProjective<Bls12_381G1> {
+    GroupProjective::new(fq(x), fq(y), Fq::one())
+}
+
+fn triple((x, y, z): (Fq, Fq, Fq)) -> GroupProjective<Bls12_381G1> {
+    GroupProjective::new(x, y, z)
+}
+
+#[test]
+fn test_expand_message_xmd() {
+    // RFC 9380, appendix K.1
+    let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+    assert_eq!(
+        expand_message_xmd::<Sha256>(b"", dst, 0x20),
+        from_hex("68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235")
+    );
+    assert_eq!(
+        expand_message_xmd::<Sha256>(b"abc", dst, 0x20),
+        from_hex("d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615")
+    );
+
+    // output blocks are not truncated early
+    let long = expand_message_xmd::<Sha256>(b"abc", dst, 0x80);
+    assert_eq!(long.len(), 0x80);
+    assert_ne!(long[..0x20], long[0x20..0x40]);
+
+    // an oversized DST is replaced by its hash
+    let long_dst = [b'a'; 256];
+    let hashed_dst = Sha256::new()
+        .chain_update(b"H2C-OVERSIZE-DST-")
+        .chain_update(long_dst)
+        .finalize();
+    assert_eq!(
+        expand_message_xmd::<Sha256>(b"abc", &long_dst, 0x20),
+        expand_message_xmd::<Sha256>(b"abc", &hashed_dst, 0x20)
+    );
+}
+
+/// RFC 9380, appendix J.9.1: the suite `BLS12381G1_XMD:SHA-256_SSWU_RO_`.
+#[test]
+fn test_bls12_381_g1() {
+    let dst = b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_";
+
+    let [u0, u1] = hash_to_field::<Bls12_381G1>(b"", dst);
+    assert_eq!(u0, fq("0ba14bd907ad64a016293ee7c2d276b8eae71f25a4b941eece7b0d89f17f75cb3ae5438a614fb61d6835ad59f29c564f"));
+    assert_eq!(u1, fq("019b9bd7979f12657976de2884c7cce192b82c177c80e0ec604436a7f538d231552f0d96d9f7babe5fa3b19b3ff25ac9"));
+    assert!(homogeneous_eq(
+        &triple(map_to_curve::<Bls12_381G1>(&u0)),
+        &affine(
+            "11a3cce7e1d90975990066b2f2643b9540fa40d6137780df4e753a8054d07580db3b7f1f03396333d4a359d1fe3766fe",
+            "0eeaf6d794e479e270da10fdaf768db4c96b650a74518fc67b04b03927754bac66f3ac720404f339ecdcc028afa091b7"
+        )
+    ));
+    assert!(homogeneous_eq(
+        &triple(map_to_curve::<Bls12_381G1>(&u1)),
+        &affine(
+            "160003aaf1632b13396dbad518effa00fff532f604de1a7fc2082ff4cb0afa2d63b2c32da1bef2bf6c5ca62dc6b72f9c",
+            "0d8bb2d14e20cf9f6036152ed386d79189415b6d015a20133acb4e019139b94e9c146aaad5817f866c95d609a361735e"
+        )
+    ));
+
+    let vectors = [
+        (
+            "",
+            "052926add2207b76ca4fa57a8734416c8dc95e24501772c814278700eed6d1e4e8cf62d9c09db0fac349612b759e79a1",
+            "08ba738453bfed09cb546dbb0783dbb3a5f1f566ed67bb6be0e8c67e2e81a4cc68ee29813bb7994998f3eae0c9c6a265",
+        ),
+        (
+            "abc",
+            "03567bc5ef9c690c2ab2ecdf6a96ef1c139cc0b2f284dca0a9a7943388a49a3aee664ba5379a7655d3c68900be2f6903",
+            "0b9c15f3fe6e5cf4211f346271d7b01c8f3b28be689c8429c85b67af215533311f0b8dfaaa154fa6b88176c229f2885d",
+        ),
+        (
+            "abcdef0123456789",
+            "11e0b079dea29a68f0383ee94fed1b940995272407e3bb916bbf268c263ddd57a6a27200a784cbc248e84f357ce82d98",
+            "03a87ae2caf14e8ee52e51fa2ed8eefe80f02457004ba4d486d6aa1f517c0889501dc7413753f9599b099ebcbbd2d709",
+        ),
+    ];
+    for (msg, x, y) in vectors {
+        let p = arkworks::hash_to_curve::<Bls12_381G1>(dst, msg.as_bytes());
+        assert!(homogeneous_eq(&p.0, &affine(x, y)));
+        assert!(is_in_subgroup(&p.0));
+    }
+}
+
+#[test]
+fn test_exceptional_inputs() {
+    // u = 0 is the exceptional case of the simplified SWU map
+    let p = triple(map_to_curve::<Bls12_381G1>(&Fq::zero()));
+    assert!(arkworks::is_on_curve_homogeneous(&p));
+    let (x, y, z) = map_to_curve::<pallas::Point>(&pallas::Base::ZERO);
+    assert!(bool::from(halo2::is_on_curve_homogeneous(
+        &HomogeneousPoint::<pallas::Point> { x, y, z }
+    )));
+
+    // a point of the kernel of the 11-isogeny, which lies on E'
+    let t = (
+        fq("010ef325dd1e98bdf0d97a4c6b7f968ed7f31f2fbff088acb39d5319cfc261ea18773405f325612742f0c5d90634bcf4"),
+        fq("0eb3efc0a8592166adb221f4b9158d9ff0d160ce6a2cc1a280b3ec28c01870fa13ca7cddd7ba78f6cd54fe36375cd5fd"),
+        Fq::one(),
+    );
+    assert!(homogeneous_eq(
+        &triple(iso_map::<Bls12_381G1>(t)),
+        &homogeneous_identity()
+    ));
+
+    // the map to E' never divides by zero
+    let (_, _, z) = map_to_curve_simple_swu::<Bls12_381G1>(&Fq::zero());
+    assert!(!z.is_zero());
+}
+
+fn pasta_base<F: halo2curves::group::ff::PrimeField<Repr = [u8; 32]>>(s: &str) -> F {
+    let mut repr = [0; 32];
+    repr.copy_from_slice(&from_hex(s));
+    repr.reverse();
+    F::from_repr(repr).unwrap()
+}
+
+fn check_pasta<C>(msg: &[u8], x: &str, y: &str)
+where
+    C: halo2::Rcb15Params + crate::hash_to_curve::SswuParams<Field = <C as CurveExt>::Base>,
+    C::Base: halo2curves::group::ff::PrimeField<Repr = [u8; 32]>,
+{
+    let expected = HomogeneousPoint::<C> {
+        x: pasta_base(x),
+        y: pasta_base(y),
+        z: C::Base::ONE,
+    };
+    assert_eq!(halo2::hash_to_curve::<C>("z.cash:test", msg), expected);
+
+    // and the same points as pasta_curves
+    for msg in [msg, b"", b"abc", b"hello"] {
+        let p = halo2::hash_to_curve::<C>("z.cash:test", msg);
+        assert_eq!(
+            p,
+            HomogeneousPoint::from(C::hash_to_curve("z.cash:test")(msg))
+        );
+        assert!(bool::from(halo2::is_in_subgroup(&p)));
+    }
+}
+
+/// The test vectors of `pasta_curves`, which take both branches of the map.
+#[test]
+fn test_pasta() {
+    check_pasta::<pallas::Point>(
+        b"Trans rights now!",
+        "1818cda31ffdc8c3ff23df3d88c26f952340257d0f187a0236695c9b640b6bd3",
+        "01e20888510123752166a0306332e126289f6f9a2774160395f2f1efc9b1280c",
+    );
+    check_pasta::<vesta::Point>(
+        b"hello",
+        "2e983e009cf3b86bc95f91b3411bd6cbd0a87f8c3c3dae80f3f2637084849204",
+        "310fb8f3316d069a1fb9374bdbc0fb1391c864a5208b2a812341db7f50b2e106",
+    );
+}
@@ -0,0 +1,98 @@
+use ark_ff::One;
+use ark_std::test_rng;
+use ark_std::UniformRand;
+use halo2curves::group::Group;
+use halo2curves::pasta::pallas;
+use halo2curves::CurveExt;
+
+use crate::arkworks::Rcb15Params;
+use crate::formulas::{self, PROGRAMS};
+use crate::halo2::{self, HOMOGENEOUS_TO_JACOBIAN, JACOBIAN_TO_HOMOGENEOUS};
+use crate::ir::{Op, Program, Step};
+use crate::opcount::{self, Formula};
+
+type Fq = ark_bn254::Fq;
+type Parameters = ark_bn254::g1::Parameters;
+
+const REPEAT: usize = 5;
+
+#[test]
+fn test_validate() {
+    let others = [
+        &formulas::CURVE_EQUATION,
+        &JACOBIAN_TO_HOMOGENEOUS,
+        &HOMOGENEOUS_TO_JACOBIAN,
+    ];
+    for program in PROGRAMS.iter().chain(others) {
+        assert_eq!(program.validate(), Ok(()));
+    }
+
+    let program = Program {
+        name: "broken",
+        inputs: &["x"],
+        steps: &[
+            Step {
+                out: "t0",
+                op: Op::Square("y"),
+            },
+            Step {
+                out: "x",
+                op: Op::Add("x", "x"),
+            },
+        ],
+        outputs: &["t1"],
+    };
+    assert!(program.validate().is_err());
+}
+
+#[test]
+fn test_eval() {
+    let mut rng = test_rng();
+
+    for _ in 0..REPEAT {
+        let v: Vec<Fq> = (0..6).map(|_| Fq::rand(&mut rng)).collect();
+        let b3 = Parameters::mul_by_b3;
+
+        let (x, y, z) = formulas::add(v[0], v[1], v[2], v[3], v[4], v[5], b3);
+        assert_eq!(formulas::ADD.eval(&v, b3), vec![x, y, z]);
+
+        let (x, y, z) = formulas::add_mixed(v[0], v[1], v[2], v[3], v[4], b3);
+        assert_eq!(formulas::ADD_MIXED.eval(&v[..5], b3), vec![x, y, z]);
+
+        let (x, y, z) = formulas::double(v[0], v[1], v[2], b3);
+        assert_eq!(formulas::DOUBLE.eval(&v[..3], b3), vec![x, y, z]);
+    }
+}
+
+#[test]
+fn test_eval_conversions() {
+    let mut rng = test_rng();
+    let unused = |_: &pallas::Base| unreachable!();
+
+    for _ in 0..REPEAT {
+        let p = pallas::Point::random(&mut rng);
+        let (x, y, z) = p.jacobian_coordinates();
+
+        let (hx, hy, hz) = halo2::jacobian_to_homogeneous(&p);
+        assert_eq!(
+            JACOBIAN_TO_HOMOGENEOUS.eval(&[x, y, z], unused),
+            vec![hx, hy, hz]
+        );
+
+        let (jx, jy, jz) = halo2::homogeneous_to_jacobian::<pallas::Point>(hx, hy, hz);
+        assert_eq!(
+            HOMOGENEOUS_TO_JACOBIAN.eval(&[hx, hy, hz], unused),
+            vec![jx, jy, jz]
+        );
+    }
+}
+
+#[test]
+fn test_op_count() {
+    for formula in Formula::ALL {
+        assert_eq!(
+            formula.program().op_count(),
+            opcount::count(formula, Fq::one(), Parameters::mul_by_b3)
+        );
+    }
+}
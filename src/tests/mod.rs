@@ -1,13 +1,71 @@
 mod ark_bls12_377;
+mod ark_bls12_377_g2;
 mod ark_bls12_381;
+mod ark_bls12_381_g2;
 mod ark_bn254;
+mod blinding;
+mod cofactor;
+mod cross_bn254;
+mod equality;
 mod halo2_bn254;
+#[cfg(feature = "halo2-chip")]
+mod halo2_chip;
 mod halo2_pallas;
 mod halo2_vesta;
+mod hash_to_curve;
+mod ir;
+mod on_curve;
+mod opcount;
+mod props;
+#[cfg(feature = "r1cs-gadget")]
+mod r1cs_gadget;
+mod random;
+mod serialize;
+mod subgroup;
+mod symbolic;
+#[cfg(feature = "trace")]
+mod trace;
 
 #[macro_export]
 macro_rules! impl_ark_rcb15_tests {
     ($proj: ident) => {
+        #[test]
+        fn test_params() {
+            let mut rng = test_rng();
+
+            assert_eq!(
+                <$proj as Rcb15Params>::B3,
+                <$proj as ModelParameters>::BaseField::from(3u64)
+                    * <$proj as SWModelParameters>::COEFF_B
+            );
+
+            // (x, y) -> (beta * x, y) is multiplication by lambda
+            for _ in 0..REPEAT {
+                let x = GroupProjective::<$proj>::rand(&mut rng)
+                    .into_affine()
+                    .into_projective();
+                let endo = GroupProjective::<$proj>::new(
+                    x.x * <$proj as Rcb15Params>::ENDO_BETA,
+                    x.y,
+                    x.z,
+                );
+                assert_eq!(endo, x.mul(<$proj as Rcb15Params>::ENDO_LAMBDA.into_repr()));
+            }
+        }
+
+        #[test]
+        fn test_mul_by_b3() {
+            let mut rng = test_rng();
+
+            for _ in 0..REPEAT {
+                let x = <$proj as ModelParameters>::BaseField::rand(&mut rng);
+                assert_eq!(
+                    <$proj as Rcb15Params>::mul_by_b3(&x),
+                    <$proj as Rcb15Params>::B3 * x
+                );
+            }
+        }
+
         #[test]
         fn test_add() {
             let mut rng = test_rng();
@@ -25,15 +83,8 @@ macro_rules! impl_ark_rcb15_tests {
                 let z = x + y;
                 let res = add::<$proj>(&x, &y);
 
-                println!(
-                    "{}",
-                    res.y * res.y * res.z
-                        - res.x * res.x * res.x
-                        - <$proj as ModelParameters>::BaseField::from(3u64) * res.z * res.z * res.z
-                );
-
+                assert!(is_on_curve_homogeneous(&res));
                 let res_affine = homogeneous_form_to_affine(&res);
-                assert!(res_affine.is_on_curve());
                 assert!(res_affine.is_in_correct_subgroup_assuming_on_curve());
 
                 #[cfg(debug_assertions)]
@@ -42,14 +93,45 @@ macro_rules! impl_ark_rcb15_tests {
                     println!("rcb15: {:?}", homogeneous_form_to_affine(&res));
                 }
 
-                assert_eq!(
-                    z.into_affine(),
-                    homogeneous_form_to_affine(&res),
+                assert!(
+                    homogeneous_eq(&jacobian_to_homogeneous(&z), &res),
                     "random add failed"
                 );
             }
         }
 
+        #[test]
+        fn test_add_mixed() {
+            let mut rng = test_rng();
+
+            for _ in 0..REPEAT {
+                let x = GroupProjective::<$proj>::rand(&mut rng)
+                    .into_affine()
+                    .into_projective();
+                let y = GroupProjective::<$proj>::rand(&mut rng).into_affine();
+
+                let res = add_mixed::<$proj>(&x, &y);
+                assert!(homogeneous_eq(
+                    &res,
+                    &jacobian_to_homogeneous(&(x + y.into_projective()))
+                ));
+                assert!(homogeneous_eq(
+                    &add_mixed(&res, &y),
+                    &add(&res, &y.into_projective())
+                ));
+
+                // P + P and P + (-P) through the mixed formula
+                assert!(homogeneous_eq(
+                    &add_mixed(&y.into_projective(), &y),
+                    &jacobian_to_homogeneous(&y.into_projective().double())
+                ));
+                assert!(homogeneous_eq(
+                    &add_mixed(&y.into_projective(), &-y),
+                    &homogeneous_identity()
+                ));
+            }
+        }
+
         #[test]
         fn test_double() {
             let mut rng = test_rng();
@@ -62,7 +144,7 @@ macro_rules! impl_ark_rcb15_tests {
                 // test doubling via addition formula
                 let res2 = add(&x, &x);
                 let res3 = x + x;
-                assert_eq!(res, res2);
+                assert!(homogeneous_eq(&res, &res2));
 
                 #[cfg(debug_assertions)]
                 {
@@ -71,8 +153,8 @@ macro_rules! impl_ark_rcb15_tests {
                     println!("rcb15 add: {:?}\n", homogeneous_form_to_affine(&res2));
                 }
 
-                assert_eq!(res3.into_affine(), homogeneous_form_to_affine(&res));
-                assert_eq!(res3.into_affine(), homogeneous_form_to_affine(&res2));
+                assert!(homogeneous_eq(&jacobian_to_homogeneous(&res3), &res));
+                assert!(homogeneous_eq(&jacobian_to_homogeneous(&res3), &res2));
             }
         }
 
@@ -95,7 +177,7 @@ macro_rules! impl_ark_rcb15_tests {
                     println!("rcb15 double: {:?}", homogeneous_form_to_affine(&res));
                 }
 
-                assert_eq!(res2.into_affine(), homogeneous_form_to_affine(&res));
+                assert!(homogeneous_eq(&jacobian_to_homogeneous(&res2), &res));
             }
         }
 
@@ -123,7 +205,208 @@ macro_rules! impl_ark_rcb15_tests {
                     println!("rcb15 double: {:?}", homogeneous_form_to_affine(&res));
                 }
 
-                assert_eq!(res2.into_affine(), homogeneous_form_to_affine(&res));
+                assert!(homogeneous_eq(&jacobian_to_homogeneous(&res2), &res));
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! impl_ark_rcb15_exceptional_tests {
+    ($proj: ident) => {
+        fn random_scaling(
+            p: &GroupProjective<$proj>,
+            rng: &mut impl ark_std::rand::Rng,
+        ) -> GroupProjective<$proj> {
+            let l = <$proj as ModelParameters>::BaseField::rand(rng);
+            GroupProjective::<$proj>::new(p.x * l, p.y * l, p.z * l)
+        }
+
+        #[test]
+        fn test_add_identity() {
+            let mut rng = test_rng();
+            let o = homogeneous_identity::<$proj>();
+
+            // O + O
+            assert!(homogeneous_eq(&add(&o, &o), &o));
+            assert!(homogeneous_eq(&add(&o, &random_scaling(&o, &mut rng)), &o));
+
+            for _ in 0..REPEAT {
+                let p = GroupProjective::<$proj>::rand(&mut rng)
+                    .into_affine()
+                    .into_projective();
+
+                // O + P and P + O
+                assert!(homogeneous_eq(&add(&o, &p), &p));
+                assert!(homogeneous_eq(&add(&p, &o), &p));
+
+                // P + (-P)
+                let neg = GroupProjective::<$proj>::new(p.x, -p.y, p.z);
+                assert!(homogeneous_eq(&add(&p, &neg), &o));
+                assert!(homogeneous_eq(&add(&neg, &p), &o));
+            }
+        }
+
+        #[test]
+        fn test_add_same_point() {
+            let mut rng = test_rng();
+
+            for _ in 0..REPEAT {
+                let p = GroupProjective::<$proj>::rand(&mut rng)
+                    .into_affine()
+                    .into_projective();
+                let q = random_scaling(&p, &mut rng);
+
+                // P + P through the addition formula, with equal and with
+                // different Z representations of P
+                let expected = jacobian_to_homogeneous(&p.double());
+                assert!(homogeneous_eq(&add(&p, &p), &expected));
+                assert!(homogeneous_eq(&add(&p, &q), &expected));
+                assert!(homogeneous_eq(&add(&q, &p), &expected));
+                assert!(homogeneous_eq(&double(&q), &expected));
+            }
+        }
+
+        #[test]
+        fn test_double_identity() {
+            let mut rng = test_rng();
+            let o = homogeneous_identity::<$proj>();
+
+            assert!(homogeneous_eq(&double(&o), &o));
+            assert!(homogeneous_eq(&double(&random_scaling(&o, &mut rng)), &o));
+        }
+
+        #[test]
+        fn test_representations() {
+            let mut rng = test_rng();
+
+            for _ in 0..REPEAT {
+                let p = GroupProjective::<$proj>::rand(&mut rng)
+                    .into_affine()
+                    .into_projective();
+                let q = GroupProjective::<$proj>::rand(&mut rng)
+                    .into_affine()
+                    .into_projective();
+                let p2 = random_scaling(&p, &mut rng);
+                let q2 = random_scaling(&q, &mut rng);
+
+                // the same point in different Z representations normalises
+                // to the same affine point
+                assert_eq!(homogeneous_form_to_affine(&p2), p.into_affine());
+
+                assert!(homogeneous_eq(&p2, &p));
+
+                // and the outputs are the same point, even though the
+                // homogeneous triples differ
+                let res = add(&p, &q);
+                let res2 = add(&p2, &q2);
+                assert!(homogeneous_eq(&res, &res2));
+                assert!(homogeneous_eq(&res, &jacobian_to_homogeneous(&(p + q))));
+                assert!(homogeneous_eq(&double(&p), &double(&p2)));
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! impl_halo2_rcb15_exceptional_tests {
+    ($point: ident) => {
+        fn random_scaling(p: &$point, rng: &mut impl ark_std::rand::RngCore) -> $point {
+            // (X : Y : Z) and (l^2 X : l^3 Y : l Z) are the same Jacobian point
+            let l = <$point as CurveExt>::Base::random(rng);
+            let (x, y, z) = p.jacobian_coordinates();
+            $point::new_jacobian(x * l.square(), y * l.square() * l, z * l).unwrap()
+        }
+
+        #[test]
+        fn test_add_identity() {
+            let mut rng = test_rng();
+            let o = $point::identity();
+
+            assert_eq!(
+                jacobian_to_homogeneous(&o).1,
+                <$point as CurveExt>::Base::ONE
+            );
+
+            // O + O
+            assert!(bool::from(add(&o, &o).is_identity()));
+            assert!(bool::from(
+                add(&o, &random_scaling(&o, &mut rng)).is_identity()
+            ));
+
+            for _ in 0..REPEAT {
+                let p = $point::random(&mut rng);
+
+                // O + P and P + O
+                assert_eq!(add(&o, &p), p);
+                assert_eq!(add(&p, &o), p);
+
+                // P + (-P)
+                assert!(bool::from(add(&p, &-p).is_identity()));
+                assert!(bool::from(add(&-p, &p).is_identity()));
+            }
+        }
+
+        #[test]
+        fn test_add_same_point() {
+            let mut rng = test_rng();
+
+            for _ in 0..REPEAT {
+                let p = $point::random(&mut rng);
+                let q = random_scaling(&p, &mut rng);
+
+                // P + P through the addition formula, with equal and with
+                // different Z representations of P
+                let expected = p.double();
+                assert_eq!(add(&p, &p), expected);
+                assert_eq!(add(&p, &q), expected);
+                assert_eq!(add(&q, &p), expected);
+                assert_eq!(double(&q), expected);
+            }
+        }
+
+        #[test]
+        fn test_double_identity() {
+            let mut rng = test_rng();
+            let o = $point::identity();
+
+            assert!(bool::from(double(&o).is_identity()));
+            assert!(bool::from(
+                double(&random_scaling(&o, &mut rng)).is_identity()
+            ));
+        }
+
+        #[test]
+        fn test_representations() {
+            let mut rng = test_rng();
+
+            for _ in 0..REPEAT {
+                let p = $point::random(&mut rng);
+                let q = $point::random(&mut rng);
+                let p2 = random_scaling(&p, &mut rng);
+                let q2 = random_scaling(&q, &mut rng);
+
+                // the same point in different Z representations normalises
+                // to the same affine point
+                assert_eq!(
+                    homogeneous_form_to_affine(&p2),
+                    homogeneous_form_to_affine(&p)
+                );
+                assert_eq!(HomogeneousPoint::from(p2), HomogeneousPoint::from(p));
+
+                // and the outputs are the same point
+                assert_eq!(
+                    HomogeneousPoint::from(add(&p, &q)),
+                    HomogeneousPoint::from(add(&p2, &q2))
+                );
+                assert_eq!(
+                    HomogeneousPoint::from(add(&p, &q)),
+                    HomogeneousPoint::from(p + q)
+                );
+                assert_eq!(
+                    HomogeneousPoint::from(double(&p)),
+                    HomogeneousPoint::from(double(&p2))
+                );
             }
         }
     };
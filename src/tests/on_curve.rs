@@ -0,0 +1,114 @@
+use ark_ec::short_weierstrass_jacobian::GroupProjective;
+use ark_ec::ProjectiveCurve;
+use ark_ff::{One, UniformRand, Zero};
+use ark_std::test_rng;
+use halo2curves::group::ff::Field;
+use halo2curves::{bn256, pasta::pallas, pasta::vesta};
+
+use crate::arkworks::{
+    self, affine_to_homogeneous_form, homogeneous_identity, is_on_curve_homogeneous,
+    is_on_curve_homogeneous_generic,
+};
+use crate::halo2::{self, HomogeneousPoint};
+
+const REPEAT: usize = 5;
+
+fn check_arkworks<P: arkworks::Rcb15Params>() {
+    let mut rng = test_rng();
+    let check = |p: &GroupProjective<P>, expected: bool| {
+        assert_eq!(is_on_curve_homogeneous(p), expected);
+        assert_eq!(is_on_curve_homogeneous_generic(p), expected);
+    };
+
+    let zero = P::BaseField::zero();
+    check(&homogeneous_identity(), true);
+    check(
+        &GroupProjective::new(zero, P::BaseField::rand(&mut rng), zero),
+        true,
+    );
+    check(&GroupProjective::new(zero, zero, zero), false);
+
+    for _ in 0..REPEAT {
+        let p = affine_to_homogeneous_form(&GroupProjective::<P>::rand(&mut rng).into_affine());
+        let s = P::BaseField::rand(&mut rng);
+        let p = GroupProjective::<P>::new(p.x * s, p.y * s, p.z * s);
+
+        check(&p, true);
+        check(
+            &GroupProjective::new(p.x, p.y + P::BaseField::one(), p.z),
+            false,
+        );
+        check(&GroupProjective::new(p.x, p.y, zero), false);
+    }
+}
+
+fn check_halo2<C: halo2::Rcb15Params>() {
+    let mut rng = test_rng();
+    let check = |p: &HomogeneousPoint<C>, expected: bool| {
+        assert_eq!(bool::from(halo2::is_on_curve_homogeneous(p)), expected);
+        assert_eq!(
+            bool::from(halo2::is_on_curve_homogeneous_generic(p)),
+            expected
+        );
+    };
+
+    let zero = C::Base::ZERO;
+    check(&HomogeneousPoint::identity(), true);
+    check(&HomogeneousPoint::from(C::identity()), true);
+    check(
+        &HomogeneousPoint {
+            x: zero,
+            y: zero,
+            z: zero,
+        },
+        false,
+    );
+
+    for _ in 0..REPEAT {
+        let p = HomogeneousPoint::from(C::random(&mut rng));
+        let s = C::Base::random(&mut rng);
+        let p = HomogeneousPoint::<C> {
+            x: p.x * s,
+            y: p.y * s,
+            z: p.z * s,
+        };
+
+        check(&p, true);
+        check(
+            &HomogeneousPoint {
+                y: p.y + C::Base::ONE,
+                ..p
+            },
+            false,
+        );
+        check(&HomogeneousPoint { z: zero, ..p }, false);
+
+        // the formulas take points off the curve without a debug assertion
+        let off_curve = HomogeneousPoint {
+            y: p.y + C::Base::ONE,
+            ..p
+        };
+        let _ = off_curve + p;
+        let _ = off_curve + off_curve;
+    }
+}
+
+#[test]
+fn test_arkworks() {
+    check_arkworks::<ark_bn254::g1::Parameters>();
+    check_arkworks::<ark_bls12_381::g1::Parameters>();
+    check_arkworks::<ark_bls12_377::g1::Parameters>();
+
+    // the point of order two of BLS12-377 is on the curve
+    type Bls12_377 = ark_bls12_377::g1::Parameters;
+    let one = <Bls12_377 as ark_ec::ModelParameters>::BaseField::one();
+    let t = GroupProjective::<Bls12_377>::new(-one, Zero::zero(), one);
+    assert!(is_on_curve_homogeneous(&t));
+}
+
+#[test]
+fn test_halo2() {
+    check_halo2::<bn256::G1>();
+    check_halo2::<pallas::Point>();
+    check_halo2::<vesta::Point>();
+}
@@ -0,0 +1,97 @@
+use crate::arkworks;
+use crate::halo2;
+use crate::opcount::{circuit_cost, CircuitCost, Coordinates, Formula, OpCount};
+
+/// Algorithm 7: 12M + 2m_b3 + 19a.
+const ADD: OpCount = OpCount {
+    mul: 12,
+    square: 0,
+    add: 14,
+    sub: 5,
+    mul_by_const: 2,
+};
+
+/// Algorithm 8: 11M + 2m_b3 + 13a.
+const ADD_MIXED: OpCount = OpCount {
+    mul: 11,
+    square: 0,
+    add: 10,
+    sub: 3,
+    mul_by_const: 2,
+};
+
+/// Algorithm 9: 6M + 2S + 1m_b3 + 9a.
+const DOUBLE: OpCount = OpCount {
+    mul: 6,
+    square: 2,
+    add: 8,
+    sub: 1,
+    mul_by_const: 1,
+};
+
+fn check(op_count: impl Fn(Formula) -> OpCount) {
+    assert_eq!(op_count(Formula::Add), ADD);
+    assert_eq!(op_count(Formula::AddMixed), ADD_MIXED);
+    assert_eq!(op_count(Formula::Double), DOUBLE);
+}
+
+#[test]
+fn test_display() {
+    assert_eq!(ADD.to_string(), "12M + 0S + 2m_b3 + 19a");
+    assert_eq!(ADD_MIXED.to_string(), "11M + 0S + 2m_b3 + 13a");
+    assert_eq!(DOUBLE.to_string(), "6M + 2S + 1m_b3 + 9a");
+}
+
+#[test]
+fn test_arkworks() {
+    check(arkworks::op_count::<ark_bn254::g1::Parameters>);
+    check(arkworks::op_count::<ark_bls12_381::g1::Parameters>);
+    check(arkworks::op_count::<ark_bls12_377::g1::Parameters>);
+}
+
+#[test]
+fn test_halo2() {
+    check(halo2::op_count::<halo2curves::bn256::G1>);
+    check(halo2::op_count::<halo2curves::pasta::pallas::Point>);
+    check(halo2::op_count::<halo2curves::pasta::vesta::Point>);
+}
+
+#[test]
+fn test_circuit_cost() {
+    for formula in Formula::ALL {
+        let count = formula.program().op_count();
+        let cost = circuit_cost(formula, Coordinates::Homogeneous);
+        assert_eq!(cost.r1cs_constraints(), count.mul + count.square);
+        assert_eq!(
+            cost.plonk_gates(),
+            formula.program().steps.len(),
+            "{}",
+            formula.program().name
+        );
+    }
+
+    // Algorithm 7 in homogeneous coordinates, and with two Jacobian inputs
+    // (2M + 1S each) and a Jacobian output (2M + 1S)
+    let add = CircuitCost {
+        nonlinear: 12,
+        linear: 19,
+        constant: 2,
+    };
+    assert_eq!(circuit_cost(Formula::Add, Coordinates::Homogeneous), add);
+    assert_eq!(
+        circuit_cost(Formula::Add, Coordinates::Jacobian),
+        CircuitCost {
+            nonlinear: 21,
+            ..add
+        }
+    );
+    assert_eq!(
+        circuit_cost(Formula::AddMixed, Coordinates::Jacobian).r1cs_constraints(),
+        11 + 6
+    );
+    assert_eq!(
+        circuit_cost(Formula::Double, Coordinates::Jacobian).r1cs_constraints(),
+        8 + 6
+    );
+    assert_eq!(add.to_string(), "12 nonlinear + 19 linear + 2 constant");
+}
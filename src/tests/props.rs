@@ -0,0 +1,215 @@
+//! Property-based tests for the group laws, shared by every curve.
+//!
+//! Points are generated as `[k]G` from a random scalar `k`, so shrinking
+//! a failing case shrinks both the points and the scalars towards small
+//! multiples of the generator. The RNG seed is fixed and failure files are
+//! disabled, so every run explores the same cases and needs no network or
+//! writable source tree.
+
+use std::ops::{Add, Mul};
+
+use proptest::test_runner::{Config, RngSeed};
+
+/// Seed for every property; change it locally to explore other cases.
+const SEED: u64 = 0x5243_4231_355f_5357;
+
+pub(crate) fn config() -> Config {
+    Config {
+        cases: 32,
+        rng_seed: RngSeed::Fixed(SEED),
+        failure_persistence: None,
+        ..Config::default()
+    }
+}
+
+/// Sum of `limbs[i] * 2^(64 * i)`, reduced into the field.
+pub(crate) fn scalar_from_limbs<F>(limbs: [u64; 4]) -> F
+where
+    F: From<u64> + Add<Output = F> + Mul<Output = F> + Copy,
+{
+    let half = F::from(1u64 << 32);
+    limbs.iter().rev().fold(F::from(0u64), |acc, &limb| {
+        acc * half * half + F::from(limb)
+    })
+}
+
+#[macro_export]
+macro_rules! impl_ark_rcb15_proptests {
+    ($proj: ident) => {
+        mod props {
+            use super::*;
+            use proptest::prelude::*;
+
+            use $crate::arkworks::affine_to_homogeneous_form;
+            use $crate::tests::props::{config, scalar_from_limbs};
+
+            type Scalar = <$proj as ModelParameters>::ScalarField;
+
+            fn scalar() -> impl Strategy<Value = Scalar> {
+                any::<[u64; 4]>().prop_map(scalar_from_limbs)
+            }
+
+            fn point(k: &Scalar) -> GroupProjective<$proj> {
+                let p = GroupProjective::<$proj>::prime_subgroup_generator().mul(k.into_repr());
+                affine_to_homogeneous_form(&p.into_affine())
+            }
+
+            proptest! {
+                #![proptest_config(config())]
+
+                #[test]
+                fn prop_add_commutative(a in scalar(), b in scalar()) {
+                    let (p, q) = (point(&a), point(&b));
+                    prop_assert!(homogeneous_eq(
+                        &add(&p, &q),
+                        &add(&q, &p)
+                    ));
+                }
+
+                #[test]
+                fn prop_add_associative(a in scalar(), b in scalar(), c in scalar()) {
+                    let (p, q, r) = (point(&a), point(&b), point(&c));
+                    prop_assert!(homogeneous_eq(
+                        &add(&add(&p, &q), &r),
+                        &add(&p, &add(&q, &r))
+                    ));
+                }
+
+                #[test]
+                fn prop_identity(a in scalar()) {
+                    let p = point(&a);
+                    let o = homogeneous_identity::<$proj>();
+                    prop_assert!(homogeneous_eq(&add(&p, &o), &p));
+                    prop_assert!(homogeneous_eq(&add(&o, &p), &p));
+                }
+
+                #[test]
+                fn prop_inverse(a in scalar()) {
+                    let p = point(&a);
+                    let neg = GroupProjective::<$proj>::new(p.x, -p.y, p.z);
+                    prop_assert!(homogeneous_eq(&add(&p, &neg), &homogeneous_identity()));
+                }
+
+                #[test]
+                fn prop_double_is_add(a in scalar()) {
+                    let p = point(&a);
+                    prop_assert!(homogeneous_eq(
+                        &double(&p),
+                        &add(&p, &p)
+                    ));
+                }
+
+                #[test]
+                fn prop_mul_distributive(k in scalar(), a in scalar(), b in scalar()) {
+                    let p = point(&k);
+                    prop_assert!(homogeneous_eq(
+                        &mul(&p, &(a + b)),
+                        &add(&mul(&p, &a), &mul(&p, &b))
+                    ));
+                }
+
+                #[test]
+                fn prop_msm_linear(
+                    k1 in scalar(),
+                    k2 in scalar(),
+                    a in scalar(),
+                    b in scalar(),
+                    c in scalar(),
+                ) {
+                    let (p, q) = (point(&k1), point(&k2));
+                    // a P + b P + c Q == (a + b) P + c Q
+                    prop_assert!(homogeneous_eq(
+                        &naive_msm(&[p, p, q], &[a, b, c]),
+                        &naive_msm(&[p, q], &[a + b, c])
+                    ));
+                    prop_assert!(homogeneous_eq(
+                        &naive_msm(&[p, q], &[a, c]),
+                        &add(&mul(&p, &a), &mul(&q, &c))
+                    ));
+                }
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! impl_halo2_rcb15_proptests {
+    ($point: ident, $scalar: ident) => {
+        mod props {
+            use super::*;
+            use proptest::prelude::*;
+
+            use $crate::tests::props::{config, scalar_from_limbs};
+
+            fn scalar() -> impl Strategy<Value = $scalar> {
+                any::<[u64; 4]>().prop_map(scalar_from_limbs)
+            }
+
+            fn point(k: &$scalar) -> $point {
+                $point::generator() * k
+            }
+
+            proptest! {
+                #![proptest_config(config())]
+
+                #[test]
+                fn prop_add_commutative(a in scalar(), b in scalar()) {
+                    let (p, q) = (point(&a), point(&b));
+                    prop_assert_eq!(add(&p, &q), add(&q, &p));
+                }
+
+                #[test]
+                fn prop_add_associative(a in scalar(), b in scalar(), c in scalar()) {
+                    let (p, q, r) = (point(&a), point(&b), point(&c));
+                    prop_assert_eq!(add(&add(&p, &q), &r), add(&p, &add(&q, &r)));
+                }
+
+                #[test]
+                fn prop_identity(a in scalar()) {
+                    let p = point(&a);
+                    let o = $point::identity();
+                    prop_assert_eq!(add(&p, &o), p);
+                    prop_assert_eq!(add(&o, &p), p);
+                }
+
+                #[test]
+                fn prop_inverse(a in scalar()) {
+                    let p = point(&a);
+                    prop_assert!(bool::from(add(&p, &-p).is_identity()));
+                }
+
+                #[test]
+                fn prop_double_is_add(a in scalar()) {
+                    let p = point(&a);
+                    prop_assert_eq!(double(&p), add(&p, &p));
+                }
+
+                #[test]
+                fn prop_mul_distributive(k in scalar(), a in scalar(), b in scalar()) {
+                    let p = point(&k);
+                    prop_assert_eq!(mul(&p, &(a + b)), add(&mul(&p, &a), &mul(&p, &b)));
+                }
+
+                #[test]
+                fn prop_msm_linear(
+                    k1 in scalar(),
+                    k2 in scalar(),
+                    a in scalar(),
+                    b in scalar(),
+                    c in scalar(),
+                ) {
+                    let (p, q) = (point(&k1), point(&k2));
+                    // a P + b P + c Q == (a + b) P + c Q
+                    prop_assert_eq!(
+                        naive_msm(&[p, p, q], &[a, b, c]),
+                        naive_msm(&[p, q], &[a + b, c])
+                    );
+                    prop_assert_eq!(
+                        naive_msm(&[p, q], &[a, c]),
+                        add(&mul(&p, &a), &mul(&q, &c))
+                    );
+                }
+            }
+        }
+    };
+}
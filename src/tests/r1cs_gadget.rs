@@ -0,0 +1,177 @@
+use ark_bls12_377::g1::Parameters;
+use ark_bls12_377::{Fq, Fr};
+use ark_ec::short_weierstrass_jacobian::{GroupAffine, GroupProjective};
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ff::{BigInteger, One, PrimeField, UniformRand, Zero};
+use ark_r1cs_std::alloc::AllocVar;
+use ark_r1cs_std::boolean::Boolean;
+use ark_r1cs_std::R1CSVar;
+use ark_relations::r1cs::ConstraintSystem;
+use ark_std::test_rng;
+
+use crate::arkworks::{
+    add, affine_to_homogeneous_form, double, homogeneous_eq, homogeneous_identity, mul, Rcb15Params,
+};
+use crate::opcount::{circuit_cost, Coordinates, Formula};
+use crate::r1cs_gadget::{num_constraints, ProjectiveVar};
+
+const REPEAT: usize = 5;
+
+type G1 = GroupProjective<Parameters>;
+
+/// A random point of the prime-order subgroup, in homogeneous form.
+fn random_point(rng: &mut impl ark_std::rand::Rng) -> G1 {
+    affine_to_homogeneous_form(&G1::rand(rng).into_affine())
+}
+
+/// A random point on the curve outside the prime-order subgroup.
+fn outside_subgroup(rng: &mut impl ark_std::rand::Rng) -> G1 {
+    loop {
+        if let Some(p) = GroupAffine::<Parameters>::get_point_from_x(Fq::rand(rng), false) {
+            if !p.is_in_correct_subgroup_assuming_on_curve() {
+                return affine_to_homogeneous_form(&p);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_add_double() {
+    let mut rng = test_rng();
+    let o = homogeneous_identity::<Parameters>();
+
+    for _ in 0..REPEAT {
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let p = random_point(&mut rng);
+        let q = random_point(&mut rng);
+        let neg_p = G1::new(p.x, -p.y, p.z);
+
+        let pv = ProjectiveVar::new_witness(cs.clone(), || Ok(p)).unwrap();
+        let qv = ProjectiveVar::new_witness(cs.clone(), || Ok(q)).unwrap();
+        let ov = ProjectiveVar::new_witness(cs.clone(), || Ok(o)).unwrap();
+        let neg_pv = ProjectiveVar::new_witness(cs.clone(), || Ok(neg_p)).unwrap();
+
+        // the gadget produces exactly the triples of the native code
+        let cases = [
+            (pv.add(&qv).unwrap(), add(&p, &q)),
+            (pv.add(&pv).unwrap(), add(&p, &p)),
+            (pv.add(&ov).unwrap(), add(&p, &o)),
+            (ov.add(&pv).unwrap(), add(&o, &p)),
+            (ov.add(&ov).unwrap(), add(&o, &o)),
+            (pv.add(&neg_pv).unwrap(), add(&p, &neg_p)),
+            (pv.double().unwrap(), double(&p)),
+            (ov.double().unwrap(), double(&o)),
+        ];
+        for (var, native) in cases {
+            let value = var.value().unwrap();
+            assert_eq!((value.x, value.y, value.z), (native.x, native.y, native.z));
+        }
+        assert!(homogeneous_eq(
+            &pv.add(&neg_pv).unwrap().value().unwrap(),
+            &o
+        ));
+        assert!(cs.is_satisfied().unwrap());
+    }
+}
+
+#[test]
+fn test_scalar_mul() {
+    let mut rng = test_rng();
+
+    for _ in 0..REPEAT {
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let p = random_point(&mut rng);
+        let scalar = Fr::rand(&mut rng);
+
+        let pv = ProjectiveVar::new_witness(cs.clone(), || Ok(p)).unwrap();
+        let bits =
+            Vec::<Boolean<Fq>>::new_witness(cs.clone(), || Ok(scalar.into_repr().to_bits_le()))
+                .unwrap();
+        let res = pv.scalar_mul_le(bits.iter()).unwrap();
+
+        assert!(homogeneous_eq(&res.value().unwrap(), &mul(&p, &scalar)));
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    // zero scalar
+    let cs = ConstraintSystem::<Fq>::new_ref();
+    let pv = ProjectiveVar::new_witness(cs.clone(), || Ok(random_point(&mut rng))).unwrap();
+    let bits = Vec::<Boolean<Fq>>::new_witness(cs.clone(), || Ok(vec![false; 8])).unwrap();
+    let res = pv.scalar_mul_le(bits.iter()).unwrap();
+    assert!(homogeneous_eq(
+        &res.value().unwrap(),
+        &homogeneous_identity()
+    ));
+    assert!(cs.is_satisfied().unwrap());
+}
+
+#[test]
+fn test_rejects_invalid() {
+    let mut rng = test_rng();
+    let p = random_point(&mut rng);
+
+    // off the curve
+    let cs = ConstraintSystem::<Fq>::new_ref();
+    let off_curve = G1::new(p.x, p.y + Fq::one(), p.z);
+    ProjectiveVar::new_witness(cs.clone(), || Ok(off_curve)).unwrap();
+    assert!(!cs.is_satisfied().unwrap());
+
+    // (0 : 0 : 0) satisfies the curve equation but is not a point; there is
+    // no 1 / Y to witness for Y != 0
+    let cs = ConstraintSystem::<Fq>::new_ref();
+    let zero = G1::new(Fq::zero(), Fq::zero(), Fq::zero());
+    assert!(ProjectiveVar::new_witness(cs, || Ok(zero)).is_err());
+
+    // on the curve, outside the prime-order subgroup: phi(Q) + [-lambda]Q
+    // is not the identity, or is a point of order two with no 1 / Y
+    for _ in 0..REPEAT {
+        let q = outside_subgroup(&mut rng);
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let res = ProjectiveVar::new_witness(cs.clone(), || Ok(q));
+        assert!(res.is_err() || !cs.is_satisfied().unwrap());
+    }
+
+    // P + T, with T of order two, would let P + (P + T) reach (0 : 0 : 0);
+    // x^2 is odd, so phi(P + T) + [x^2](P + T) = phi(T) + T, which is of
+    // order two and has Y = 0
+    let t = G1::new(-Fq::one(), Fq::zero(), Fq::one());
+    let cs = ConstraintSystem::<Fq>::new_ref();
+    assert!(ProjectiveVar::new_input(cs, || Ok(add(&p, &t))).is_err());
+}
+
+#[test]
+fn test_num_constraints() {
+    // one constraint per multiplication or squaring, see tests/opcount.rs
+    assert_eq!(num_constraints::<Parameters>(Formula::Add), 12);
+    assert_eq!(num_constraints::<Parameters>(Formula::AddMixed), 11);
+    assert_eq!(num_constraints::<Parameters>(Formula::Double), 8);
+    for formula in Formula::ALL {
+        assert_eq!(
+            num_constraints::<Parameters>(formula),
+            circuit_cost(formula, Coordinates::Homogeneous).r1cs_constraints()
+        );
+    }
+
+    // a witness costs the curve equation (3M + 3S), its check and Y != 0,
+    // then the subgroup check: nothing for the leading zeros of x^2, 6 for
+    // adding P to the constant identity at its leading one, a doubling and
+    // an addition for each later bit, the addition of phi(P) and three checks
+    let cs = ConstraintSystem::<Fq>::new_ref();
+    let p = random_point(&mut test_rng());
+    let pv = ProjectiveVar::new_witness(cs.clone(), || Ok(p)).unwrap();
+    let x2_bits = (-Parameters::ENDO_LAMBDA).into_repr().num_bits() as usize;
+    assert_eq!(x2_bits, 127);
+    assert_eq!(
+        cs.num_constraints(),
+        8 + 6 + (x2_bits - 1) * (8 + 12) + 12 + 3
+    );
+    assert!(cs.is_satisfied().unwrap());
+
+    // at most a double, an addition and three selections per bit; the
+    // first steps on the constant identity are cheaper
+    let bits = Vec::<Boolean<Fq>>::new_witness(cs.clone(), || Ok(vec![true; 16])).unwrap();
+    let before = cs.num_constraints();
+    pv.scalar_mul_le(bits.iter()).unwrap();
+    assert!(cs.num_constraints() - before <= 16 * (8 + 12 + 3));
+    assert!(cs.is_satisfied().unwrap());
+}
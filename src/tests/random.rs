@@ -0,0 +1,101 @@
+use ark_ec::short_weierstrass_jacobian::GroupProjective;
+use ark_ec::ProjectiveCurve;
+use ark_ff::{One, UniformRand};
+use ark_std::test_rng;
+use halo2curves::bn256;
+use halo2curves::group::ff::Field;
+use halo2curves::group::Group;
+use halo2curves::pasta::{pallas, vesta};
+
+use crate::arkworks;
+use crate::halo2::{self, HomogeneousPoint};
+
+const REPEAT: usize = 10;
+
+fn check_arkworks<P: arkworks::Rcb15Params>() {
+    let mut rng = test_rng();
+    for _ in 0..REPEAT {
+        // the same seed draws the same point, before the scaling
+        let mut seeded = rng.clone();
+        let p = arkworks::HomogeneousPoint::<P>::rand(&mut rng);
+        assert!(p.0.z.is_one());
+        assert!(arkworks::is_in_subgroup(&p.0));
+
+        let q = arkworks::HomogeneousPoint::<P>::rand_with_random_z(&mut seeded);
+        assert!(!q.0.z.is_one());
+        assert!(arkworks::is_in_subgroup(&q.0));
+        assert_eq!(q, p);
+        assert_eq!(q.to_affine(), p.to_affine());
+        assert_eq!(q.0.x, p.0.x * q.0.z);
+        assert_eq!(q.0.y, p.0.y * q.0.z);
+    }
+
+    // not a constant
+    let mut rng = test_rng();
+    let p = arkworks::HomogeneousPoint::<P>::rand(&mut rng);
+    let q = arkworks::HomogeneousPoint::<P>::rand(&mut rng);
+    assert_ne!(p, q);
+    assert_ne!(
+        p.to_affine(),
+        GroupProjective::<P>::prime_subgroup_generator().into_affine()
+    );
+}
+
+#[test]
+fn test_arkworks_rand() {
+    check_arkworks::<ark_bn254::g1::Parameters>();
+    check_arkworks::<ark_bls12_381::g1::Parameters>();
+    check_arkworks::<ark_bls12_381::g2::Parameters>();
+    check_arkworks::<ark_bls12_377::g1::Parameters>();
+    check_arkworks::<ark_bls12_377::g2::Parameters>();
+}
+
+fn check_halo2<C>()
+where
+    C: halo2::Rcb15Params,
+    HomogeneousPoint<C>: Group<Scalar = C::ScalarExt>,
+{
+    let mut rng = test_rng();
+    for _ in 0..REPEAT {
+        // the same seed draws the same point, before the scaling
+        let mut seeded = rng.clone();
+        let p = <HomogeneousPoint<C> as Group>::random(&mut rng);
+        assert_eq!(p.z, C::Base::ONE);
+        assert!(bool::from(halo2::is_in_subgroup(&p)));
+
+        let q = HomogeneousPoint::<C>::random_with_random_z(&mut seeded);
+        assert_ne!(q.z, C::Base::ONE);
+        assert!(bool::from(halo2::is_in_subgroup(&q)));
+        assert_eq!(q, p);
+        assert_eq!(q.to_curve().to_affine(), p.to_curve().to_affine());
+        assert_eq!(q.x, p.x * q.z);
+        assert_eq!(q.y, p.y * q.z);
+
+        // the group law agrees with the native one
+        let k = C::ScalarExt::random(&mut rng);
+        assert_eq!((p + q).to_curve(), p.to_curve() + q.to_curve());
+        assert_eq!((p - q).to_curve(), p.to_curve() - q.to_curve());
+        assert_eq!((-q).to_curve(), -q.to_curve());
+        assert_eq!(p.double().to_curve(), p.to_curve().double());
+        assert_eq!((q * k).to_curve(), q.to_curve() * k);
+        assert_eq!([p, q, q].iter().sum::<HomogeneousPoint<C>>(), p + q + q);
+
+        let mut r = p;
+        r += q;
+        r -= p;
+        r *= k;
+        assert_eq!(r, q * k);
+    }
+    assert!(bool::from(HomogeneousPoint::<C>::identity().is_identity()));
+    assert_eq!(
+        <HomogeneousPoint<C> as Group>::generator().to_curve(),
+        C::generator()
+    );
+}
+
+#[test]
+fn test_halo2_random() {
+    check_halo2::<bn256::G1>();
+    check_halo2::<pallas::Point>();
+    check_halo2::<vesta::Point>();
+}
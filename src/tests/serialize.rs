@@ -0,0 +1,184 @@
+use ark_ec::short_weierstrass_jacobian::{GroupAffine, GroupProjective};
+use ark_ec::ProjectiveCurve;
+use ark_ff::{One, UniformRand, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::test_rng;
+use halo2curves::group::ff::{Field as _, PrimeField};
+use halo2curves::group::{GroupEncoding, UncompressedEncoding};
+use halo2curves::{bn256, pasta::pallas};
+
+use crate::arkworks::{self, Rcb15Params};
+use crate::halo2::{self, HomogeneousBytes};
+
+const REPEAT: usize = 5;
+
+/// A random point of the subgroup with a random Z.
+fn ark_random_point<P: Rcb15Params>(
+    rng: &mut impl ark_std::rand::Rng,
+) -> arkworks::HomogeneousPoint<P> {
+    let p = arkworks::HomogeneousPoint::from_affine(&GroupProjective::<P>::rand(rng).into_affine());
+    let s = P::BaseField::rand(rng);
+    arkworks::HomogeneousPoint(GroupProjective::new(p.0.x * s, p.0.y * s, p.0.z * s))
+}
+
+fn ark_uncompressed<P: Rcb15Params>(p: &arkworks::HomogeneousPoint<P>) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    p.serialize_uncompressed(&mut bytes).unwrap();
+    assert_eq!(bytes.len(), p.uncompressed_size());
+    bytes
+}
+
+fn ark_roundtrip<P: Rcb15Params>() {
+    let mut rng = test_rng();
+
+    for p in (0..REPEAT)
+        .map(|_| ark_random_point::<P>(&mut rng))
+        .chain([arkworks::HomogeneousPoint::identity()])
+    {
+        // compressed: the same affine point
+        let mut bytes = Vec::new();
+        p.serialize(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), p.serialized_size());
+        let q = arkworks::HomogeneousPoint::<P>::deserialize(&bytes[..]).unwrap();
+        assert_eq!(q, p);
+
+        // uncompressed: the same triple
+        let bytes = ark_uncompressed(&p);
+        let q = arkworks::HomogeneousPoint::<P>::deserialize_uncompressed(&bytes[..]).unwrap();
+        assert_eq!((q.0.x, q.0.y, q.0.z), (p.0.x, p.0.y, p.0.z));
+    }
+}
+
+/// Uncompressed encodings of triples that are not points of the subgroup
+/// are rejected, unless unchecked.
+fn ark_rejects<P: Rcb15Params>(invalid: &[GroupProjective<P>]) {
+    for p in invalid {
+        let bytes = ark_uncompressed(&arkworks::HomogeneousPoint(*p));
+        assert!(arkworks::HomogeneousPoint::<P>::deserialize_uncompressed(&bytes[..]).is_err());
+        assert!(arkworks::HomogeneousPoint::<P>::deserialize_unchecked(&bytes[..]).is_ok());
+    }
+}
+
+fn ark_invalid<P: Rcb15Params>() -> Vec<GroupProjective<P>> {
+    let mut rng = test_rng();
+    let p = ark_random_point::<P>(&mut rng).0;
+    let zero = P::BaseField::zero();
+    vec![
+        GroupProjective::new(zero, zero, zero),
+        GroupProjective::new(p.x, p.y + P::BaseField::one(), p.z),
+        GroupProjective::new(p.x, p.y, zero),
+    ]
+}
+
+/// A point on the curve outside the prime-order subgroup.
+fn ark_outside_subgroup<P: Rcb15Params>() -> GroupProjective<P> {
+    let mut rng = test_rng();
+    loop {
+        let x = P::BaseField::rand(&mut rng);
+        if let Some(p) = GroupAffine::<P>::get_point_from_x(x, false) {
+            if !p.is_in_correct_subgroup_assuming_on_curve() {
+                return arkworks::affine_to_homogeneous_form(&p);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_arkworks() {
+    type Bn254 = ark_bn254::g1::Parameters;
+    type Bls12_381 = ark_bls12_381::g1::Parameters;
+    type Bls12_377 = ark_bls12_377::g1::Parameters;
+
+    ark_roundtrip::<Bn254>();
+    ark_roundtrip::<Bls12_381>();
+    ark_roundtrip::<Bls12_377>();
+
+    ark_rejects(&ark_invalid::<Bn254>());
+    ark_rejects(&ark_invalid::<Bls12_381>());
+    ark_rejects(&ark_invalid::<Bls12_377>());
+
+    // the cofactors of BLS12 G1 are not one
+    ark_rejects(&[ark_outside_subgroup::<Bls12_381>()]);
+    let one = <Bls12_377 as ark_ec::ModelParameters>::BaseField::one();
+    let two_torsion = GroupProjective::<Bls12_377>::new(-one, Zero::zero(), one);
+    ark_rejects(&[two_torsion, ark_outside_subgroup::<Bls12_377>()]);
+
+    // BLS12-381 G1: 48-byte compressed points and field elements
+    let p = ark_random_point::<Bls12_381>(&mut test_rng());
+    assert_eq!(p.serialized_size(), 48);
+    assert_eq!(p.uncompressed_size(), 3 * 48);
+}
+
+fn halo2_roundtrip<C: halo2::Rcb15Params>()
+where
+    C::Base: PrimeField<Repr = [u8; 32]>,
+{
+    let mut rng = test_rng();
+
+    for _ in 0..REPEAT {
+        let p = C::random(&mut rng);
+        let s = C::Base::random(&mut rng);
+        let h = halo2::HomogeneousPoint::from(p);
+        let h = halo2::HomogeneousPoint::<C> {
+            x: h.x * s,
+            y: h.y * s,
+            z: h.z * s,
+        };
+
+        let bytes = h.to_bytes();
+        assert_eq!(bytes.as_ref(), p.to_bytes().as_ref());
+        let q = halo2::HomogeneousPoint::<C>::from_bytes(&bytes).unwrap();
+        assert_eq!(q, h);
+
+        let q = halo2::HomogeneousPoint::<C>::from_uncompressed(&h.to_uncompressed()).unwrap();
+        assert_eq!((q.x, q.y, q.z), (h.x, h.y, h.z));
+    }
+
+    let o = halo2::HomogeneousPoint::<C>::identity();
+    let q = halo2::HomogeneousPoint::<C>::from_uncompressed(&o.to_uncompressed()).unwrap();
+    assert!(bool::from(q.to_curve().is_identity()));
+    let q = halo2::HomogeneousPoint::<C>::from_bytes(&o.to_bytes()).unwrap();
+    assert!(bool::from(q.to_curve().is_identity()));
+}
+
+fn halo2_rejects<C: halo2::Rcb15Params>()
+where
+    C::Base: PrimeField<Repr = [u8; 32]>,
+{
+    let h = halo2::HomogeneousPoint::from(C::random(test_rng()));
+    let zero = C::Base::ZERO;
+    let invalid = [
+        halo2::HomogeneousPoint::<C> {
+            x: zero,
+            y: zero,
+            z: zero,
+        },
+        halo2::HomogeneousPoint::<C> {
+            y: h.y + C::Base::ONE,
+            ..h
+        },
+    ];
+    for p in invalid {
+        let bytes = p.to_uncompressed();
+        assert!(bool::from(
+            halo2::HomogeneousPoint::<C>::from_uncompressed(&bytes).is_none()
+        ));
+        assert!(bool::from(
+            halo2::HomogeneousPoint::<C>::from_uncompressed_unchecked(&bytes).is_some()
+        ));
+    }
+
+    // not a canonical field element
+    let bytes = HomogeneousBytes([0xff; 96]);
+    assert!(bool::from(
+        halo2::HomogeneousPoint::<C>::from_uncompressed_unchecked(&bytes).is_none()
+    ));
+}
+
+#[test]
+fn test_halo2() {
+    halo2_roundtrip::<bn256::G1>();
+    halo2_roundtrip::<pallas::Point>();
+    halo2_rejects::<bn256::G1>();
+    halo2_rejects::<pallas::Point>();
+}
@@ -0,0 +1,125 @@
+use ark_ec::short_weierstrass_jacobian::{GroupAffine, GroupProjective};
+use ark_ec::{ModelParameters, ProjectiveCurve};
+use ark_ff::{One, UniformRand, Zero};
+use ark_std::test_rng;
+use halo2curves::group::ff::Field as _;
+use halo2curves::{bn256, pasta::pallas, pasta::vesta};
+
+use crate::arkworks::{
+    self, affine_to_homogeneous_form, homogeneous_identity, is_in_subgroup, is_in_subgroup_naive,
+};
+use crate::halo2::{self, HomogeneousPoint};
+
+const REPEAT: usize = 5;
+
+type Bn254 = ark_bn254::g1::Parameters;
+type Bls12_381G1 = ark_bls12_381::g1::Parameters;
+type Bls12_381G2 = ark_bls12_381::g2::Parameters;
+type Bls12_377G1 = ark_bls12_377::g1::Parameters;
+type Bls12_377G2 = ark_bls12_377::g2::Parameters;
+
+fn random_scaling<P: arkworks::Rcb15Params>(
+    p: &GroupProjective<P>,
+    rng: &mut impl ark_std::rand::Rng,
+) -> GroupProjective<P> {
+    let s = P::BaseField::rand(rng);
+    GroupProjective::new(p.x * s, p.y * s, p.z * s)
+}
+
+/// Points on the curve outside the prime-order subgroup, with random Z.
+pub(crate) fn outside_subgroup<P: arkworks::Rcb15Params>(n: usize) -> Vec<GroupProjective<P>> {
+    let mut rng = test_rng();
+    let mut points = Vec::new();
+    while points.len() < n {
+        let x = P::BaseField::rand(&mut rng);
+        if let Some(p) = GroupAffine::<P>::get_point_from_x(x, false) {
+            if !p.is_in_correct_subgroup_assuming_on_curve() {
+                points.push(random_scaling(&affine_to_homogeneous_form(&p), &mut rng));
+            }
+        }
+    }
+    points
+}
+
+/// The dispatched and the naive checks agree on `p`.
+fn check<P: arkworks::Rcb15Params>(p: &GroupProjective<P>, expected: bool) {
+    assert_eq!(is_in_subgroup(p), expected);
+    assert_eq!(P::is_in_subgroup_assuming_on_curve(p), expected);
+    assert_eq!(is_in_subgroup_naive(p), expected);
+}
+
+fn check_arkworks<P: arkworks::Rcb15Params>(cofactor_one: bool) {
+    let mut rng = test_rng();
+
+    check(&homogeneous_identity::<P>(), true);
+    check(
+        &random_scaling(&homogeneous_identity::<P>(), &mut rng),
+        true,
+    );
+
+    for _ in 0..REPEAT {
+        let p = affine_to_homogeneous_form(&GroupProjective::<P>::rand(&mut rng).into_affine());
+        let p = random_scaling(&p, &mut rng);
+        check(&p, true);
+
+        // not on the curve
+        let q = GroupProjective::<P>::new(p.x, p.y + P::BaseField::one(), p.z);
+        assert!(!is_in_subgroup(&q));
+        let zero = P::BaseField::zero();
+        assert!(!is_in_subgroup(&GroupProjective::<P>::new(
+            zero, zero, zero
+        )));
+    }
+
+    if !cofactor_one {
+        for p in outside_subgroup::<P>(REPEAT) {
+            check(&p, false);
+        }
+    }
+}
+
+#[test]
+fn test_arkworks() {
+    check_arkworks::<Bn254>(true);
+    check_arkworks::<Bls12_381G1>(false);
+    check_arkworks::<Bls12_381G2>(false);
+    check_arkworks::<Bls12_377G1>(false);
+    check_arkworks::<Bls12_377G2>(false);
+
+    // the point of order two of BLS12-377 G1
+    let one = <Bls12_377G1 as ModelParameters>::BaseField::one();
+    check(
+        &GroupProjective::<Bls12_377G1>::new(-one, Zero::zero(), one),
+        false,
+    );
+}
+
+fn check_halo2<C: halo2::Rcb15Params>() {
+    let mut rng = test_rng();
+
+    for _ in 0..REPEAT {
+        let p = C::random(&mut rng);
+        assert!(bool::from(halo2::is_in_subgroup_naive(&p)));
+        assert!(bool::from(halo2::is_in_subgroup(&HomogeneousPoint::from(
+            p
+        ))));
+
+        let h = HomogeneousPoint::from(p);
+        let q = HomogeneousPoint::<C> {
+            y: h.y + C::Base::ONE,
+            ..h
+        };
+        assert!(!bool::from(halo2::is_in_subgroup(&q)));
+    }
+    assert!(bool::from(halo2::is_in_subgroup_naive(&C::identity())));
+    assert!(bool::from(halo2::is_in_subgroup(
+        &HomogeneousPoint::<C>::identity()
+    )));
+}
+
+#[test]
+fn test_halo2() {
+    check_halo2::<bn256::G1>();
+    check_halo2::<pallas::Point>();
+    check_halo2::<vesta::Point>();
+}
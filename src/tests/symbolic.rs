@@ -0,0 +1,399 @@
+//! Symbolic verification of the formulas in `crate::formulas`.
+//!
+//! The formulas are evaluated over multivariate polynomials with integer
+//! coefficients in X1, Y1, Z1, X2, Y2, Z2 and b, and compared with the
+//! textbook group law modulo the curve equation. Each formula is run both
+//! through its straight-line program and through the generated function.
+//!
+//! With Z = 1 the curve equation is Y^2 = X^3 + b, and rewriting Y^2 to
+//! X^3 + b gives a normal form (every monomial has Y-degree at most one),
+//! so two polynomials agree on the curve iff their normal forms are equal.
+//! A separate check shows that the outputs are homogeneous of one degree in
+//! each input point, which extends the result to any Z.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::formulas::{self, Rcb15Field};
+use crate::ir::Program;
+
+const X1: usize = 0;
+const Y1: usize = 1;
+const Z1: usize = 2;
+const X2: usize = 3;
+const Y2: usize = 4;
+const Z2: usize = 5;
+const B: usize = 6;
+const NVARS: usize = 7;
+
+type Monomial = [u32; NVARS];
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Poly(BTreeMap<Monomial, i128>);
+
+impl Poly {
+    fn constant(c: i128) -> Self {
+        let mut p = Poly::default();
+        p.insert([0; NVARS], c);
+        p
+    }
+
+    fn var(i: usize) -> Self {
+        let mut m = [0; NVARS];
+        m[i] = 1;
+        let mut p = Poly::default();
+        p.insert(m, 1);
+        p
+    }
+
+    fn insert(&mut self, m: Monomial, c: i128) {
+        let entry = self.0.entry(m).or_insert(0);
+        *entry += c;
+        if *entry == 0 {
+            self.0.remove(&m);
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Rewrite Yi^2 to Xi^3 + b for both points, assuming Z1 = Z2 = 1.
+    fn reduce(&self) -> Self {
+        let mut res = Poly::default();
+        let mut todo: Vec<(Monomial, i128)> = self.0.iter().map(|(m, c)| (*m, *c)).collect();
+        while let Some((m, c)) = todo.pop() {
+            match [(X1, Y1), (X2, Y2)].into_iter().find(|&(_, y)| m[y] >= 2) {
+                Some((x, y)) => {
+                    let mut base = m;
+                    base[y] -= 2;
+                    let mut cubic = base;
+                    cubic[x] += 3;
+                    let mut constant = base;
+                    constant[B] += 1;
+                    todo.push((cubic, c));
+                    todo.push((constant, c));
+                }
+                None => res.insert(m, c),
+            }
+        }
+        res
+    }
+
+    /// The total degree in `vars` of every monomial, if they all agree.
+    fn degree_in(&self, vars: &[usize]) -> Option<u32> {
+        let mut degrees = self
+            .0
+            .keys()
+            .map(|m| vars.iter().map(|&v| m[v]).sum::<u32>());
+        let first = degrees.next()?;
+        degrees.all(|d| d == first).then_some(first)
+    }
+}
+
+fn monomial_product(m1: &Monomial, m2: &Monomial) -> Monomial {
+    let mut m = *m1;
+    m.iter_mut().zip(m2.iter()).for_each(|(a, b)| *a += b);
+    m
+}
+
+impl Add for &Poly {
+    type Output = Poly;
+
+    fn add(self, rhs: &Poly) -> Poly {
+        let mut res = self.clone();
+        for (m, c) in &rhs.0 {
+            res.insert(*m, *c);
+        }
+        res
+    }
+}
+
+impl Neg for &Poly {
+    type Output = Poly;
+
+    fn neg(self) -> Poly {
+        Poly(self.0.iter().map(|(m, c)| (*m, -c)).collect())
+    }
+}
+
+impl Sub for &Poly {
+    type Output = Poly;
+
+    fn sub(self, rhs: &Poly) -> Poly {
+        self + &-rhs
+    }
+}
+
+impl Mul for &Poly {
+    type Output = Poly;
+
+    fn mul(self, rhs: &Poly) -> Poly {
+        let mut res = Poly::default();
+        for (m1, c1) in &self.0 {
+            for (m2, c2) in &rhs.0 {
+                res.insert(monomial_product(m1, m2), c1 * c2);
+            }
+        }
+        res
+    }
+}
+
+thread_local! {
+    static ARENA: RefCell<Vec<Poly>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A handle to a polynomial in a thread-local arena, so that polynomials
+/// are `Copy` like the field elements the formulas are written for.
+#[derive(Clone, Copy, Debug)]
+struct Sym(usize);
+
+impl Sym {
+    fn new(p: Poly) -> Self {
+        ARENA.with(|arena| {
+            let mut arena = arena.borrow_mut();
+            arena.push(p);
+            Sym(arena.len() - 1)
+        })
+    }
+
+    fn var(i: usize) -> Self {
+        Sym::new(Poly::var(i))
+    }
+
+    fn constant(c: i128) -> Self {
+        Sym::new(Poly::constant(c))
+    }
+
+    fn poly(self) -> Poly {
+        ARENA.with(|arena| arena.borrow()[self.0].clone())
+    }
+}
+
+impl Add for Sym {
+    type Output = Sym;
+
+    fn add(self, rhs: Sym) -> Sym {
+        Sym::new(&self.poly() + &rhs.poly())
+    }
+}
+
+impl Sub for Sym {
+    type Output = Sym;
+
+    fn sub(self, rhs: Sym) -> Sym {
+        Sym::new(&self.poly() - &rhs.poly())
+    }
+}
+
+impl Mul for Sym {
+    type Output = Sym;
+
+    fn mul(self, rhs: Sym) -> Sym {
+        Sym::new(&self.poly() * &rhs.poly())
+    }
+}
+
+impl Rcb15Field for Sym {
+    fn square(&self) -> Sym {
+        *self * *self
+    }
+}
+
+fn mul_by_b3(x: &Sym) -> Sym {
+    *x * Sym::constant(3) * Sym::var(B)
+}
+
+type Point = (Poly, Poly, Poly);
+
+fn to_poly((x, y, z): (Sym, Sym, Sym)) -> Point {
+    (x.poly(), y.poly(), z.poly())
+}
+
+/// Run `program` through the IR interpreter, and check that the generated
+/// Rust function gives the same polynomials.
+fn run(program: Program, inputs: &[Sym], generated: (Sym, Sym, Sym)) -> Point {
+    let res = program.eval(inputs, mul_by_b3);
+    let res = (res[0].poly(), res[1].poly(), res[2].poly());
+    assert_eq!(res, to_poly(generated));
+    res
+}
+
+fn add(p1: (Sym, Sym, Sym), p2: (Sym, Sym, Sym)) -> Point {
+    let generated = formulas::add(p1.0, p1.1, p1.2, p2.0, p2.1, p2.2, mul_by_b3);
+    run(
+        formulas::ADD,
+        &[p1.0, p1.1, p1.2, p2.0, p2.1, p2.2],
+        generated,
+    )
+}
+
+fn add_mixed(p1: (Sym, Sym, Sym), p2: (Sym, Sym)) -> Point {
+    let generated = formulas::add_mixed(p1.0, p1.1, p1.2, p2.0, p2.1, mul_by_b3);
+    run(
+        formulas::ADD_MIXED,
+        &[p1.0, p1.1, p1.2, p2.0, p2.1],
+        generated,
+    )
+}
+
+fn double(p: (Sym, Sym, Sym)) -> Point {
+    let generated = formulas::double(p.0, p.1, p.2, mul_by_b3);
+    run(formulas::DOUBLE, &[p.0, p.1, p.2], generated)
+}
+
+/// The affine points (X1, Y1) and (X2, Y2) with Z = 1.
+fn affine_points() -> ((Sym, Sym, Sym), (Sym, Sym, Sym)) {
+    let one = Sym::constant(1);
+    (
+        (Sym::var(X1), Sym::var(Y1), one),
+        (Sym::var(X2), Sym::var(Y2), one),
+    )
+}
+
+/// `a == b` on the curve.
+fn assert_equal_on_curve(a: &Poly, b: &Poly) {
+    assert!((a - b).reduce().is_zero());
+}
+
+/// Y^2 Z = X^3 + b Z^3 holds for the output.
+fn assert_on_curve((x, y, z): &Point) {
+    let b = Poly::var(B);
+    let lhs = &(y * y) * z;
+    let rhs = &(&(x * x) * x) + &(&(&b * z) * &(z * z));
+    assert_equal_on_curve(&lhs, &rhs);
+}
+
+/// X3 / Z3 == x_num / den and Y3 / Z3 == y_num / den, without dividing.
+fn assert_affine((x3, y3, z3): &Point, x_num: &Poly, x_den: &Poly, y_num: &Poly, y_den: &Poly) {
+    assert!(!z3.reduce().is_zero());
+    assert_equal_on_curve(&(x3 * x_den), &(x_num * z3));
+    assert_equal_on_curve(&(y3 * y_den), &(y_num * z3));
+}
+
+/// The chord rule: lambda = (y2 - y1) / (x2 - x1), x3 = lambda^2 - x1 - x2
+/// and y3 = lambda (x1 - x3) - y1.
+fn assert_chord(res: &Point) {
+    let (x1, y1, x2, y2) = (Poly::var(X1), Poly::var(Y1), Poly::var(X2), Poly::var(Y2));
+    let n = &y2 - &y1;
+    let d = &x2 - &x1;
+    let d2 = &d * &d;
+    let x_num = &(&n * &n) - &(&(&x1 + &x2) * &d2);
+    let y_num = &(&n * &(&(&x1 * &d2) - &x_num)) - &(&y1 * &(&d2 * &d));
+    assert_affine(res, &x_num, &d2, &y_num, &(&d2 * &d));
+}
+
+/// The tangent rule: lambda = 3 x^2 / 2 y, x3 = lambda^2 - 2 x and
+/// y3 = lambda (x - x3) - y.
+fn assert_tangent(res: &Point) {
+    let (x, y) = (Poly::var(X1), Poly::var(Y1));
+    let c = Poly::constant;
+    let x2 = &x * &x;
+    let y2 = &y * &y;
+    let x_den = &c(4) * &y2;
+    let x_num = &(&c(9) * &(&x2 * &x2)) - &(&c(8) * &(&x * &y2));
+    let y_num = &(&(&c(3) * &x2) * &(&(&x * &x_den) - &x_num)) - &(&c(8) * &(&y2 * &y2));
+    assert_affine(res, &x_num, &x_den, &y_num, &(&c(8) * &(&y2 * &y)));
+}
+
+/// Every output coordinate is homogeneous of the same degree in `vars`.
+fn assert_homogeneous((x, y, z): &Point, vars: &[usize]) {
+    let degree = x.degree_in(vars);
+    assert!(degree.is_some());
+    assert_eq!(y.degree_in(vars), degree);
+    assert_eq!(z.degree_in(vars), degree);
+}
+
+fn projective_points() -> ((Sym, Sym, Sym), (Sym, Sym, Sym)) {
+    (
+        (Sym::var(X1), Sym::var(Y1), Sym::var(Z1)),
+        (Sym::var(X2), Sym::var(Y2), Sym::var(Z2)),
+    )
+}
+
+#[test]
+fn test_homogeneous() {
+    let (p1, p2) = projective_points();
+
+    let res = add(p1, p2);
+    assert_homogeneous(&res, &[X1, Y1, Z1]);
+    assert_homogeneous(&res, &[X2, Y2, Z2]);
+
+    let res = add_mixed(p1, (p2.0, p2.1));
+    assert_homogeneous(&res, &[X1, Y1, Z1]);
+
+    assert_homogeneous(&double(p1), &[X1, Y1, Z1]);
+}
+
+#[test]
+fn test_add() {
+    let (p1, p2) = affine_points();
+    let res = add(p1, p2);
+    assert_chord(&res);
+    assert_on_curve(&res);
+}
+
+#[test]
+fn test_add_mixed() {
+    let (p1, p2) = affine_points();
+    let res = add_mixed(p1, (p2.0, p2.1));
+    assert_chord(&res);
+    assert_on_curve(&res);
+}
+
+#[test]
+fn test_double() {
+    let (p, _) = affine_points();
+    let res = double(p);
+    assert_tangent(&res);
+    assert_on_curve(&res);
+}
+
+#[test]
+fn test_curve_equation() {
+    let (p, _) = affine_points();
+    let generated = formulas::curve_equation(p.0, p.1, p.2, mul_by_b3);
+    let res = formulas::CURVE_EQUATION.eval(&[p.0, p.1, p.2], mul_by_b3);
+    let (lhs, rhs) = (res[0].poly(), res[1].poly());
+    assert_eq!(
+        (lhs.clone(), rhs.clone()),
+        (generated.0.poly(), generated.1.poly())
+    );
+
+    // 3 Y^2 Z == 3 X^3 + 3b Z^3 holds on the curve, and not identically
+    assert_equal_on_curve(&lhs, &rhs);
+    assert!(!(&lhs - &rhs).is_zero());
+}
+
+#[test]
+fn test_exceptional_cases() {
+    let (p, _) = affine_points();
+    let neg = (p.0, Sym::constant(0) - p.1, p.2);
+    let o = (Sym::constant(0), Sym::constant(1), Sym::constant(0));
+
+    // P + P follows the tangent rule, through both addition formulas
+    assert_tangent(&add(p, p));
+    assert_tangent(&add_mixed(p, (p.0, p.1)));
+
+    // P + (-P) = O, i.e. Z3 = 0 and Y3 != 0
+    for (x3, y3, z3) in [add(p, neg), add_mixed(p, (neg.0, neg.1))] {
+        assert!(x3.reduce().is_zero());
+        assert!(!y3.reduce().is_zero());
+        assert!(z3.reduce().is_zero());
+    }
+
+    // P + O = O + P = P
+    for (x3, y3, z3) in [add(p, o), add(o, p)] {
+        assert!(!z3.reduce().is_zero());
+        assert_equal_on_curve(&x3, &(&Poly::var(X1) * &z3));
+        assert_equal_on_curve(&y3, &(&Poly::var(Y1) * &z3));
+    }
+
+    // O + O = O and [2]O = O
+    for (x3, y3, z3) in [add(o, o), double(o)] {
+        assert!(x3.is_zero());
+        assert!(!y3.is_zero());
+        assert!(z3.is_zero());
+    }
+}
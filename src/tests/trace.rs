@@ -0,0 +1,130 @@
+use ark_std::test_rng;
+use ark_std::UniformRand;
+use halo2curves::group::Group;
+use halo2curves::pasta::pallas;
+
+use crate::arkworks::{self, Rcb15Params as _};
+use crate::formulas::{self, ADD, DOUBLE, PROGRAMS};
+use crate::halo2::{self, Rcb15Params as _};
+use crate::ir::Op;
+
+type Fq = ark_bn254::Fq;
+type Parameters = ark_bn254::g1::Parameters;
+
+const REPEAT: usize = 5;
+
+fn v_of(rng: &mut impl ark_std::rand::Rng) -> Vec<Fq> {
+    (0..6).map(|_| Fq::rand(rng)).collect()
+}
+
+#[test]
+fn test_trace_values() {
+    let mut rng = test_rng();
+    let b3 = Parameters::mul_by_b3;
+
+    for _ in 0..REPEAT {
+        let v = v_of(&mut rng);
+        for program in PROGRAMS {
+            let inputs = &v[..program.inputs.len()];
+            let trace = program.trace(inputs, b3);
+
+            // inputs first, then one entry per step, named as in the source
+            assert_eq!(
+                trace.entries.len(),
+                program.inputs.len() + program.steps.len()
+            );
+            for (entry, name) in trace.entries.iter().zip(program.inputs) {
+                assert_eq!(entry.name, *name);
+                assert_eq!(entry.op, None);
+            }
+            for (entry, step) in trace.entries[program.inputs.len()..]
+                .iter()
+                .zip(program.steps)
+            {
+                assert_eq!(entry.name, step.out);
+                assert_eq!(entry.op, Some(step.op));
+            }
+            assert_eq!(trace.outputs(), program.eval(inputs, b3));
+        }
+    }
+
+    // a few values of the addition, computed by hand
+    let trace = ADD.trace(&v_of(&mut rng), b3);
+    let get = |name| trace.get(name).unwrap();
+    assert_eq!(get("t0_1"), get("x1") * get("x2"));
+    assert_eq!(get("t3_8"), get("t3_6") - get("t4_7"));
+    let (x3, y3, z3) = formulas::add(
+        get("x1"),
+        get("y1"),
+        get("z1"),
+        get("x2"),
+        get("y2"),
+        get("z2"),
+        b3,
+    );
+    assert_eq!((get("x3_27"), get("y3_30"), get("z3_33")), (x3, y3, z3));
+    assert_eq!(trace.get("undefined"), None);
+}
+
+#[test]
+fn test_trace_on_curve() {
+    let mut rng = test_rng();
+    let b3 = Parameters::mul_by_b3;
+
+    for _ in 0..REPEAT {
+        let p = arkworks::HomogeneousPoint::<Parameters>::rand_with_random_z(&mut rng).0;
+        let q = arkworks::HomogeneousPoint::<Parameters>::rand_with_random_z(&mut rng).0;
+        let trace = ADD.trace(&[p.x, p.y, p.z, q.x, q.y, q.z], b3);
+        assert!(trace.outputs_on_curve(b3));
+        assert!(DOUBLE.trace(&[p.x, p.y, p.z], b3).outputs_on_curve(b3));
+
+        // an input off the curve gives an output off the curve
+        let trace = DOUBLE.trace(&[p.x, p.y + Fq::from(1u64), p.z], b3);
+        assert!(!trace.outputs_on_curve(b3));
+    }
+
+    // the halo2 fields, from Jacobian points
+    let b3 = pallas::Point::mul_by_b3;
+    for _ in 0..REPEAT {
+        let (x, y, z) = halo2::jacobian_to_homogeneous(&pallas::Point::random(&mut rng));
+        assert!(DOUBLE.trace(&[x, y, z], b3).outputs_on_curve(b3));
+    }
+
+    // the identity is on the curve
+    let o = arkworks::homogeneous_identity::<Parameters>();
+    let trace = DOUBLE.trace(&[o.x, o.y, o.z], Parameters::mul_by_b3);
+    assert!(trace.outputs_on_curve(Parameters::mul_by_b3));
+}
+
+#[test]
+fn test_trace_display_and_diff() {
+    let mut rng = test_rng();
+    let b3 = Parameters::mul_by_b3;
+    let v = v_of(&mut rng);
+    let trace = ADD.trace(&v, b3);
+
+    let printed = trace.to_string();
+    let lines: Vec<&str> = printed.lines().collect();
+    assert_eq!(lines[0], "add");
+    assert_eq!(lines[1], format!("  x1 = {:?}", v[0]));
+    assert!(printed.contains(&format!(
+        "  t3_8 = sub(t3_6, t4_7) = {:?}\n",
+        trace.get("t3_8").unwrap()
+    )));
+    assert_eq!(*lines.last().unwrap(), "outputs: x3_27, y3_30, z3_33");
+    assert_eq!(Op::MulB3("t2_3").to_string(), "mul_b3(t2_3)");
+
+    // the same run has no differences
+    assert_eq!(trace.diff(&ADD.trace(&v, b3)), "");
+
+    // changing z2 only changes the values that depend on it
+    let mut w = v.clone();
+    w[5] += Fq::from(1u64);
+    let other = ADD.trace(&w, b3);
+    let diff = other.diff(&trace);
+    assert!(diff.starts_with(&format!("- z2 = {:?}\n+ z2 = {:?}\n", v[5], w[5])));
+    assert!(!diff.contains("t0_1 ="));
+    assert!(!diff.contains("t1_2 ="));
+    assert!(diff.contains("- t2_3 = mul(z1, z2)"));
+    assert!(diff.contains("+ x3_27 ="));
+}
@@ -0,0 +1,132 @@
+//! Traces of the intermediate values of a formula, with the `trace` feature.
+//!
+//! [`Program::trace`] evaluates a formula like [`Program::eval`] but keeps
+//! every value under its SSA name (`t3_8`, `x3_27`, ...). A [`Trace`] checks
+//! the curve equation on its outputs, prints one value per line so that two
+//! runs can be compared with `diff`, and lists the values that differ from a
+//! reference trace with [`Trace::diff`].
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::formulas::{self, Rcb15Field};
+use crate::ir::{Op, Program};
+
+/// One value of a trace: an input, or the result of a step.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TraceEntry<F> {
+    pub name: &'static str,
+    /// The step that computed the value, `None` for an input.
+    pub op: Option<Op>,
+    pub value: F,
+}
+
+/// Every value of one run of a [`Program`], inputs first, in order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Trace<F> {
+    pub program: Program,
+    pub entries: Vec<TraceEntry<F>>,
+}
+
+impl Program {
+    /// [`Program::eval`], recording every intermediate value.
+    pub fn trace<F: Rcb15Field>(&self, inputs: &[F], mul_by_b3: impl Fn(&F) -> F) -> Trace<F> {
+        let values = self.eval_all(inputs, mul_by_b3);
+        let ops = self
+            .inputs
+            .iter()
+            .map(|_| None)
+            .chain(self.steps.iter().map(|step| Some(step.op)));
+        Trace {
+            program: *self,
+            entries: values
+                .into_iter()
+                .zip(ops)
+                .map(|((name, value), op)| TraceEntry { name, op, value })
+                .collect(),
+        }
+    }
+}
+
+impl<F: Rcb15Field> Trace<F> {
+    /// The latest value named `name`, if any.
+    pub fn get(&self, name: &str) -> Option<F> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|entry| entry.name == name)
+            .map(|entry| entry.value)
+    }
+
+    /// The outputs of the program, as returned by [`Program::eval`].
+    pub fn outputs(&self) -> Vec<F> {
+        self.program
+            .outputs
+            .iter()
+            .map(|name| self.get(name).expect("outputs are defined"))
+            .collect()
+    }
+
+    /// Whether the outputs (X, Y, Z) satisfy Y^2 Z = X^3 + b Z^3, with
+    /// [`formulas::curve_equation`]. As with the equation itself, (0 : 0 : 0)
+    /// passes.
+    ///
+    /// Panics if the program does not output a point, i.e. three values.
+    pub fn outputs_on_curve(&self, mul_by_b3: impl Fn(&F) -> F) -> bool
+    where
+        F: PartialEq,
+    {
+        let outputs = self.outputs();
+        assert_eq!(
+            outputs.len(),
+            3,
+            "{} does not output a point",
+            self.program.name
+        );
+        let (lhs, rhs) = formulas::curve_equation(outputs[0], outputs[1], outputs[2], mul_by_b3);
+        lhs == rhs
+    }
+}
+
+impl<F: fmt::Debug + PartialEq> Trace<F> {
+    /// The values that differ from `reference`, one pair of lines each: the
+    /// reference value after `-` and this trace's after `+`. Empty if the two
+    /// runs agree. Values are matched by position, so the traces must come
+    /// from the same program.
+    pub fn diff(&self, reference: &Trace<F>) -> String {
+        assert_eq!(
+            self.program.name, reference.program.name,
+            "traces of different programs"
+        );
+        let mut res = String::new();
+        for (entry, expected) in self.entries.iter().zip(&reference.entries) {
+            if entry.value != expected.value {
+                res += &format!("- {}\n+ {}\n", expected, entry);
+            }
+        }
+        res
+    }
+}
+
+/// `t3_8 = sub(t3_6, t4_7) = <value>`, or `x1 = <value>` for an input.
+impl<F: fmt::Debug> fmt::Display for TraceEntry<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.op {
+            Some(op) => write!(f, "{} = {} = {:?}", self.name, op, self.value),
+            None => write!(f, "{} = {:?}", self.name, self.value),
+        }
+    }
+}
+
+/// The program name, then one value per line and the names of the outputs.
+impl<F: fmt::Debug> fmt::Display for Trace<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.program.name)?;
+        for entry in &self.entries {
+            writeln!(f, "  {}", entry)?;
+        }
+        write!(f, "outputs: {}", self.program.outputs.join(", "))
+    }
+}